@@ -1,174 +1,350 @@
 use raylib::prelude::*;
 use raylib::audio::RaylibAudio;
 
+use crate::assets::{Assets, TextureId};
+use crate::render::atlas::SpriteId;
+use crate::render::camera::CameraRig;
+use crate::audio::manager::AudioManager;
+use crate::audio::playlist::Playlist;
+use crate::audio::sfx::{Sfx, SfxPlayer};
 use crate::config;
-use crate::rng;
+use crate::debug::DebugOverlay;
+use crate::debug::console::{Command, Console};
 use crate::input::Input;
 use crate::game::world::World;
 use crate::game::effects::MusicMode;
+use crate::game::autopilot;
+use crate::game::daily::DailyChallenge;
+use crate::game::level::Level;
+use crate::game::objects::FallingObject;
+use crate::game::mode::RunSetup;
+use crate::game::versus::Versus;
+use crate::net::protocol::{MatchInfo, MatchKind};
+use crate::net::session::{NetSession, NetStatus};
+use crate::profile::Profile;
 use crate::render;
+use crate::render::viewport::Viewport;
+use crate::rng;
+use crate::settings::Settings;
 use crate::ui;
 use crate::state::menu::{MenuAction, MenuState};
 use crate::state::pause::{PauseState, PauseAction};
-use crate::state::leaderboard::{LeaderboardState, LeaderboardAction};
+use crate::state::leaderboard::{Board, LeaderboardState, LeaderboardAction, Unranked};
 use crate::state::game_over::{GameOverState, GameOverAction};
+use crate::state::level_select::{LevelSelectAction, LevelSelectState};
+use crate::state::versus_over::{VersusOverAction, VersusOverState};
+use crate::state::lobby::{LobbyAction, LobbyState};
+use crate::state::settings::{SettingsAction, SettingsState};
 
 enum Screen {
     Menu,
     Playing,
     Paused,
     Leaderboard,
+    LevelSelect,
     GameOver,
+    Versus,
+    VersusOver,
+    Lobby,
+    Settings,
 }
 
 pub fn run() {
     let (mut rl, thread) = raylib::init()
         .size(config::SCREEN_W, config::SCREEN_H)
         .title("Bucket Catch")
+        .resizable()
         .build();
 
-    rl.set_target_fps(config::TARGET_FPS);
+    let mut settings = Settings::load();
+    settings.apply_window(&mut rl, None);
+    let mut applied_settings = settings;
+    rl.set_window_min_size(config::WINDOW_MIN_W, config::WINDOW_MIN_H);
+    // Everything is drawn at the virtual resolution, then scaled into the window.
+    let mut target = rl
+        .load_render_texture(&thread, config::SCREEN_W as u32, config::SCREEN_H as u32)
+        .expect("create render texture");
+    target
+        .texture()
+        .set_texture_filter(&thread, TextureFilter::TEXTURE_FILTER_BILINEAR);
     let audio = RaylibAudio::init_audio_device().expect("init audio device");
 
-    //access to the assets texture
-    let bucket_texture = rl
-        .load_texture(&thread, "src/assets/cat/bucket.png")
-        .expect("load bucket texture");
-    let normal_texture = rl
-        .load_texture(&thread, "src/assets/cat/normalneko.png")
-        .expect("load normal neko texture");
-    let angel_texture = rl
-        .load_texture(&thread, "src/assets/cat/angelneko.png")
-        .expect("load angel neko texture");
-    let devil_texture = rl
-        .load_texture(&thread, "src/assets/cat/devilneko.png")
-        .expect("load devil neko texture");
-    let logo_texture = rl
-        .load_texture(&thread, "src/assets/UI/bucket-logo.png")
-        .expect("load logo texture");
-    let bg_texture = rl
-        .load_texture(&thread, "src/assets/UI/play_background.png")
-        .expect("load background texture");
-    let crying_cat_texture = rl
-        .load_texture(&thread, "src/assets/cat/crying_cat.png")
-        .expect("load crying cat texture");
-    let music_default = audio
-        .new_music("src/assets/sound_effects/sakura-default-music.mp3")
-        .expect("load default music");
-    
-    let alt_files = [
-        "coffee-time-bgm.mp3",
-        "chipi_chapa.mp3",
-        "happy_cat.mp3",
-        "maxwell_cat.mp3",
-        "rickroll.mp3",
-        "uiia_cat.mp3",
-    ];
-    let mut music_alt_list = Vec::new();
-    for file in alt_files.iter() {
-        music_alt_list.push(
-            audio
-                .new_music(&format!("src/assets/sound_effects/{}", file))
-                .expect("load alternate music"),
-        );
-    }
+    let mut assets = Assets::load(&mut rl, &thread);
+    let mut sfx = SfxPlayer::load(&audio, &assets);
+    let mut music = AudioManager::load(&audio, &assets, &Playlist::load(&assets));
 
     let mut screen = Screen::Menu;
+    let mut profile = Profile::load();
     let mut menu = MenuState::new();
+    menu.set_daily_played(profile.daily_played(DailyChallenge::today().date));
     let pause_menu = PauseState::new();
     let mut leaderboard = LeaderboardState::new();
+    let mut level_select = LevelSelectState::new();
+    let levels = Level::load_all(&assets);
+    let mut current_level: Option<usize> = None;
     let mut game_over = GameOverState::new();
     let mut world = create_world(
         config::SCREEN_W as f32,
         config::SCREEN_H as f32,
-        &bucket_texture,
+        &assets,
+        music.swap_count(),
+        RunSetup::new(menu.rules(), menu.mode()),
     );
-    let mut current_music = MusicMode::Default;
-    let mut music_playing = false;
+    let mut run_board: Result<Board, Unranked> = Ok(Board::Mode(menu.rules(), menu.mode()));
+    let mut versus: Option<Versus> = None;
+    let mut versus_over = VersusOverState::new();
+    let mut lobby = LobbyState::new();
+    let mut net: Option<NetSession> = None;
+    let mut net_match = false;
+    let mut autopilot_enabled = false;
+    let mut show_landing_markers = false;
+    let mut camera = CameraRig::new();
+    let mut versus_cameras = [CameraRig::new(), CameraRig::new()];
+    let mut debug = DebugOverlay::new();
+    let mut console = Console::new();
+    let mut time_scale = 1.0;
+    let settings_menu = SettingsState::new();
+    let mut settings_from_pause = false;
+    let mut settings_changed = false;
 
     while !rl.window_should_close() {
         let dt = rl.get_frame_time();
-        let screen_w = rl.get_screen_width() as f32;
-        let screen_h = rl.get_screen_height() as f32;
-        let mouse = rl.get_mouse_position();
+        debug.record(dt);
+        if rl.is_key_pressed(KeyboardKey::KEY_F11) {
+            settings.fullscreen = !settings.fullscreen;
+            settings_changed = true;
+        }
+        // Window changes wait until no frame is being drawn.
+        if settings_changed {
+            settings.apply_window(&mut rl, Some(&applied_settings));
+            applied_settings = settings;
+            settings.save();
+            settings_changed = false;
+        }
+        let screen_w = config::SCREEN_W as f32;
+        let screen_h = config::SCREEN_H as f32;
+        let viewport = Viewport::fit(rl.get_screen_width() as f32, rl.get_screen_height() as f32);
+        let mouse = viewport.to_virtual(rl.get_mouse_position());
         let clicked = rl.is_mouse_button_pressed(MouseButton::MOUSE_BUTTON_LEFT);
         let font = rl.get_font_default();
 
         if let Screen::Playing = screen {
+            if rl.is_key_pressed(KeyboardKey::KEY_F3) {
+                debug.toggle();
+            }
             // Check for pause button click BEFORE update
             let pause_btn_rect = Rectangle::new(screen_w - 50.0, 10.0, 40.0, 40.0);
-            if clicked && pause_btn_rect.check_collision_point_rec(mouse) {
+            if let Some(session) = &mut net {
+                // Both peers step the same fixed ticks; nobody can pause a LAN match.
+                session.poll(dt);
+                session.advance(dt, Input::gather(&rl, settings.controls), |inputs| {
+                    if !world.game_over_trigger {
+                        world.update(
+                            &inputs,
+                            config::NET_TICK,
+                            config::SCREEN_W as f32,
+                            config::SCREEN_H as f32,
+                        );
+                    }
+                    world.state_hash()
+                });
+                if world.game_over_trigger {
+                    finish_run(&world, run_board, &levels, current_level, &mut profile, &mut game_over);
+                    net = None;
+                    screen = Screen::GameOver;
+                }
+            } else if clicked && pause_btn_rect.check_collision_point_rec(mouse) {
                 screen = Screen::Paused;
             } else {
-                let input = Input::gather(&rl);
-                world.update(&rl, input, dt, screen_w, screen_h);
+                if rl.is_key_pressed(KeyboardKey::KEY_F2) {
+                    autopilot_enabled = !autopilot_enabled;
+                }
+                if rl.is_key_pressed(KeyboardKey::KEY_F4) {
+                    show_landing_markers = !show_landing_markers;
+                }
+                if let Some(command) = console.update_input(&mut rl) {
+                    let reply = run_command(command, &mut world, &mut time_scale, &assets, music.swap_count());
+                    console.log(reply);
+                    run_board = Err(Unranked::Debug);
+                }
+                // The autopilot and the timescale outlive the run they were set in, so any
+                // run they touch stays off the boards and earns no stars.
+                if autopilot_enabled || time_scale != 1.0 {
+                    run_board = Err(Unranked::Debug);
+                }
+                let mut inputs: Vec<Input> = if world.players.len() > 1 {
+                    (0..world.players.len())
+                        .map(|player| Input::gather_player(&rl, player))
+                        .collect()
+                } else {
+                    vec![Input::gather(&rl, settings.controls)]
+                };
+                if autopilot_enabled {
+                    inputs[0].move_x = autopilot::steer(&world, screen_w);
+                }
+                // Hit-stop only holds local runs; lockstep and versus keep ticking. The
+                // console holds the run too while it has the keyboard.
+                let held = console.is_open() || (settings.hit_stop && camera.frozen());
+                if !held {
+                    world.update(&inputs, dt * time_scale, screen_w, screen_h);
+                }
                 if world.game_over_trigger {
-                    game_over.set_score(world.score());
+                    finish_run(&world, run_board, &levels, current_level, &mut profile, &mut game_over);
                     screen = Screen::GameOver;
                 }
             }
         }
         
-        if let Screen::GameOver = screen {
-             game_over.update_input(&mut rl);
+        if let Screen::Versus = screen
+            && let Some(versus) = &mut versus
+        {
+            if let Some(session) = &mut net {
+                session.poll(dt);
+                session.advance(dt, Input::gather(&rl, settings.controls), |inputs| {
+                    versus.update(
+                        inputs,
+                        config::NET_TICK,
+                        config::SCREEN_W as f32 / 2.0,
+                        config::SCREEN_H as f32,
+                    );
+                    versus.state_hash()
+                });
+            } else {
+                let inputs = [Input::gather_player(&rl, 0), Input::gather_player(&rl, 1)];
+                versus.update(inputs, dt, screen_w / 2.0, screen_h);
+            }
+            if let Some(outcome) = versus.outcome() {
+                versus_over.set_result(outcome, versus.scores());
+                net = None;
+                screen = Screen::VersusOver;
+            }
         }
 
-        let should_play_music = matches!(screen, Screen::Playing | Screen::Paused);
-        if should_play_music {
-            let desired = world.music_mode();
-            if !music_playing {
-                match desired {
-                    MusicMode::Default => music_default.play_stream(),
-                    MusicMode::Alternate(idx) => {
-                        let len = music_alt_list.len();
-                        if let Some(m) = music_alt_list.get_mut(idx % len) {
-                            m.play_stream();
-                        }
-                    }
-                }
-                current_music = desired;
-                music_playing = true;
-            } else if desired != current_music {
-                match current_music {
-                    MusicMode::Default => music_default.stop_stream(),
-                    MusicMode::Alternate(idx) => {
-                         let len = music_alt_list.len();
-                         if let Some(m) = music_alt_list.get_mut(idx % len) {
-                            m.stop_stream();
+        // A dropped or desynced LAN match ends back in the lobby with the reason.
+        if let Some(NetStatus::Failed(reason)) = net.as_ref().map(|session| session.status().clone()) {
+            lobby.set_status(&reason, false);
+            net = None;
+            screen = Screen::Lobby;
+        }
+
+        if let Screen::Lobby = screen {
+            lobby.update_input(&mut rl);
+            if let Some(session) = &mut net {
+                session.poll(dt);
+                if *session.status() == NetStatus::Running
+                    && let Some(info) = session.info()
+                {
+                    let setup = RunSetup {
+                        seed: info.seed,
+                        ..RunSetup::versus(info.rules, info.mode)
+                    };
+                    match info.kind {
+                        MatchKind::Versus => {
+                            let mut created = create_versus(
+                                config::SCREEN_W as f32,
+                                config::SCREEN_H as f32,
+                                &assets,
+                                info.music_tracks as usize,
+                                setup,
+                            );
+                            for world in &mut created.worlds {
+                                use_match_sizes(world, &info);
+                            }
+                            versus = Some(created);
+                            screen = Screen::Versus;
                         }
-                    }
-                }
-                match desired {
-                    MusicMode::Default => music_default.play_stream(),
-                    MusicMode::Alternate(idx) => {
-                         let len = music_alt_list.len();
-                         if let Some(m) = music_alt_list.get_mut(idx % len) {
-                            m.play_stream();
+                        MatchKind::CoOp => {
+                            world = create_world(
+                                config::SCREEN_W as f32,
+                                config::SCREEN_H as f32,
+                                &assets,
+                                info.music_tracks as usize,
+                                RunSetup {
+                                    players: 2,
+                                    versus: false,
+                                    ..setup
+                                },
+                            );
+                            use_match_sizes(&mut world, &info);
+                            run_board = Err(Unranked::Lan);
+                            current_level = None;
+                            screen = Screen::Playing;
                         }
                     }
+                    net_match = true;
+                    lobby.set_status("", false);
+                    assets.set_background(&mut rl, &thread, None);
+                    music.use_default_track(&assets, None);
                 }
-                current_music = desired;
             }
+        }
 
-            match current_music {
-                MusicMode::Default => music_default.update_stream(),
-                MusicMode::Alternate(idx) => {
-                     let len = music_alt_list.len();
-                     if let Some(m) = music_alt_list.get_mut(idx % len) {
-                        m.update_stream();
-                    }
+        if let Screen::GameOver = screen {
+             game_over.update_input(&mut rl);
+        }
+
+        let mut events = world.take_events();
+        for event in &events {
+            camera.react(*event);
+        }
+        if let Some(versus) = &mut versus {
+            for (side, rig) in versus.worlds.iter_mut().zip(&mut versus_cameras) {
+                let side_events = side.take_events();
+                for event in &side_events {
+                    rig.react(*event);
                 }
+                events.extend(side_events);
             }
-        } else if music_playing {
-            music_default.stop_stream();
-            for m in music_alt_list.iter_mut() {
-                m.stop_stream();
+        }
+        camera.update(dt);
+        for rig in &mut versus_cameras {
+            rig.update(dt);
+        }
+        sfx.set_volume(settings.sfx_volume);
+        for effect in events.into_iter().filter_map(Sfx::for_event) {
+            sfx.play(effect);
+            if effect.ducks_music() {
+                music.duck(config::SFX_DUCK_TIME);
             }
-            music_playing = false;
-            current_music = MusicMode::Default;
+        }
+        // Presses from last frame's buttons.
+        if ui::take_button_press() {
+            sfx.play(Sfx::Click);
         }
 
-        let mut d = rl.begin_drawing(&thread);
+        // Each screen just says what it wants to hear; the manager handles the transitions.
+        match (&screen, &versus) {
+            (Screen::Versus, Some(versus)) => {
+                music.set_pitch(versus.music_pitch());
+                music.set_intensity(versus.music_intensity());
+                music.play(versus.music_mode());
+            }
+            (Screen::Playing, _) => {
+                music.set_pitch(world.music_pitch());
+                music.set_intensity(world.music_intensity());
+                music.play(world.music_mode());
+            }
+            (Screen::Paused, _) => music.pause(),
+            (Screen::Settings, _) if settings_from_pause => music.pause(),
+            (
+                Screen::Menu
+                | Screen::Settings
+                | Screen::Leaderboard
+                | Screen::LevelSelect
+                | Screen::Lobby,
+                _,
+            ) => {
+                music.set_pitch(1.0);
+                music.set_intensity(0.0);
+                music.play(MusicMode::Menu);
+            }
+            _ => music.stop(),
+        }
+        music.set_volume(settings.music_volume);
+        music.update(dt);
+
+        let mut frame = rl.begin_drawing(&thread);
+        let mut d = frame.begin_texture_mode(&thread, &mut target);
         match screen {
             Screen::Menu => {
                 let action = menu.update_and_draw(
@@ -178,14 +354,73 @@ pub fn run() {
                     mouse,
                     clicked,
                     &font,
-                    &logo_texture,
+                    &assets,
                 );
 
                 match action {
-                    MenuAction::Start => {
-                        world = create_world(screen_w, screen_h, &bucket_texture);
+                    MenuAction::Start { players } => {
+                        world = create_world(
+                            screen_w,
+                            screen_h,
+                            &assets,
+                            music.swap_count(),
+                            RunSetup {
+                                players,
+                                ..RunSetup::new(menu.rules(), menu.mode())
+                            },
+                        );
+                        // A shared co-op score does not belong on the single-player boards.
+                        run_board = if players == 1 {
+                            Ok(Board::Mode(menu.rules(), menu.mode()))
+                        } else {
+                            Err(Unranked::CoOp)
+                        };
+                        current_level = None;
+                        assets.set_background(&mut d, &thread, None);
+                        music.use_default_track(&assets, None);
+                        screen = Screen::Playing;
+                    }
+                    MenuAction::Daily => {
+                        let challenge = DailyChallenge::today();
+                        // Only the first daily run of the day counts; later ones are practice.
+                        run_board = if profile.daily_played(challenge.date) {
+                            Err(Unranked::DailyReplay)
+                        } else {
+                            profile.record_daily(challenge.date);
+                            menu.set_daily_played(true);
+                            Ok(Board::Daily(challenge.date))
+                        };
+                        world = create_world(
+                            screen_w,
+                            screen_h,
+                            &assets,
+                            music.swap_count(),
+                            RunSetup::daily(&challenge),
+                        );
+                        current_level = None;
+                        assets.set_background(&mut d, &thread, None);
+                        music.use_default_track(&assets, None);
                         screen = Screen::Playing;
                     }
+                    MenuAction::Campaign => screen = Screen::LevelSelect,
+                    MenuAction::Versus => {
+                        versus = Some(create_versus(
+                            screen_w,
+                            screen_h,
+                            &assets,
+                            music.swap_count(),
+                            RunSetup::versus(menu.rules(), menu.mode()),
+                        ));
+                        net_match = false;
+                        assets.set_background(&mut d, &thread, None);
+                        music.use_default_track(&assets, None);
+                        screen = Screen::Versus;
+                    }
+                    MenuAction::Lan => screen = Screen::Lobby,
+                    MenuAction::Settings => {
+                        settings_from_pause = false;
+                        screen = Screen::Settings;
+                    }
                     MenuAction::Leaderboard => {
                         leaderboard.reload();
                         screen = Screen::Leaderboard;
@@ -199,116 +434,452 @@ pub fn run() {
                     LeaderboardAction::None => {},
                  }
             }
+            Screen::LevelSelect => {
+                match level_select.update_and_draw(
+                    &mut d,
+                    screen_w,
+                    screen_h,
+                    mouse,
+                    clicked,
+                    &font,
+                    &levels,
+                    &profile,
+                ) {
+                    LevelSelectAction::Play(index) => {
+                        let level = &levels[index];
+                        world = create_world(
+                            screen_w,
+                            screen_h,
+                            &assets,
+                            music.swap_count(),
+                            RunSetup::level(level),
+                        );
+                        run_board = Err(Unranked::Level);
+                        current_level = Some(index);
+                        assets.set_background(&mut d, &thread, level.background.as_deref());
+                        music.use_default_track(&assets, level.music.as_deref());
+                        screen = Screen::Playing;
+                    }
+                    LevelSelectAction::Back => screen = Screen::Menu,
+                    LevelSelectAction::None => {}
+                }
+            }
             Screen::Playing => {
                 // d.clear_background(Color::BLACK);
-                let bg_scale = (screen_w / bg_texture.width as f32).max(screen_h / bg_texture.height as f32);
-                let bg_dest_w = bg_texture.width as f32 * bg_scale;
-                let bg_dest_h = bg_texture.height as f32 * bg_scale;
-                let bg_dest_x = (screen_w - bg_dest_w) / 2.0;
-                let bg_dest_y = (screen_h - bg_dest_h) / 2.0;
-                d.draw_texture_pro(
-                    &bg_texture,
-                    Rectangle::new(0.0, 0.0, bg_texture.width as f32, bg_texture.height as f32),
-                    Rectangle::new(bg_dest_x, bg_dest_y, bg_dest_w, bg_dest_h),
-                    Vector2::new(0.0, 0.0),
-                    0.0,
-                    Color::WHITE,
-                );
-
-                render::draw_world(
+                draw_background(
                     &mut d,
-                    &world,
-                    &bucket_texture,
-                    &normal_texture,
-                    &angel_texture,
-                    &devil_texture,
-                    &crying_cat_texture,
+                    assets.texture(TextureId::Background),
+                    Rectangle::new(0.0, 0.0, screen_w, screen_h),
+                    world.anger_stage().background_tint(),
                 );
+
+                {
+                    let view = camera.camera(&world, 0.0, screen_h, settings.camera_motion.scale());
+                    let mut shaken = d.begin_mode2D(view);
+                    render::draw_world(
+                        &mut shaken,
+                        &world,
+                        &assets,
+                        render::DrawOptions {
+                            landing_markers: show_landing_markers,
+                            palette: settings.palette,
+                        },
+                    );
+                    if debug.visible() {
+                        debug.draw_hitboxes(&mut shaken, &world);
+                    }
+                }
+                if debug.visible() {
+                    debug.draw_panel(&mut d, &world, time_scale);
+                }
+                if net.is_none() {
+                    console.draw(&mut d, screen_w);
+                }
                 
-                // Draw Pause Button
-                let pause_btn_rect = Rectangle::new(screen_w - 50.0, 10.0, 40.0, 40.0);
-                ui::draw_button(
-                    &mut d,
-                    pause_btn_rect,
-                    "||",
-                    mouse,
-                    false,
-                    &font,
-                    config::COLOR_LIGHT_BG,
-                    config::COLOR_LIGHT_HOVER,
-                    config::COLOR_ACCENT_TEXT,
-                    config::COLOR_ACCENT_BORDER,
-                );
+                if let Some(session) = &net {
+                    if session.waiting() {
+                        draw_net_waiting(&mut d, screen_w, screen_h, &font);
+                    }
+                } else {
+                    // Draw Pause Button
+                    let pause_btn_rect = Rectangle::new(screen_w - 50.0, 10.0, 40.0, 40.0);
+                    ui::draw_button(
+                        &mut d,
+                        pause_btn_rect,
+                        "||",
+                        mouse,
+                        false,
+                        &font,
+                        config::COLOR_LIGHT_BG,
+                        config::COLOR_LIGHT_HOVER,
+                        config::COLOR_ACCENT_TEXT,
+                        config::COLOR_ACCENT_BORDER,
+                    );
+                }
             }
             Screen::Paused => {
                 // Draw world as background
-                let bg_scale = (screen_w / bg_texture.width as f32).max(screen_h / bg_texture.height as f32);
-                let bg_dest_w = bg_texture.width as f32 * bg_scale;
-                let bg_dest_h = bg_texture.height as f32 * bg_scale;
-                let bg_dest_x = (screen_w - bg_dest_w) / 2.0;
-                let bg_dest_y = (screen_h - bg_dest_h) / 2.0;
-                d.draw_texture_pro(
-                    &bg_texture,
-                    Rectangle::new(0.0, 0.0, bg_texture.width as f32, bg_texture.height as f32),
-                    Rectangle::new(bg_dest_x, bg_dest_y, bg_dest_w, bg_dest_h),
-                    Vector2::new(0.0, 0.0),
-                    0.0,
-                    Color::WHITE,
+                draw_background(
+                    &mut d,
+                    assets.texture(TextureId::Background),
+                    Rectangle::new(0.0, 0.0, screen_w, screen_h),
+                    world.anger_stage().background_tint(),
                 );
 
                 render::draw_world(
                     &mut d,
                     &world,
-                    &bucket_texture,
-                    &normal_texture,
-                    &angel_texture,
-                    &devil_texture,
-                    &crying_cat_texture,
+                    &assets,
+                    render::DrawOptions {
+                        landing_markers: show_landing_markers,
+                        palette: settings.palette,
+                    },
                 );
 
                 match pause_menu.update_and_draw(&mut d, world.score(), screen_w, screen_h, mouse, clicked) {
                     PauseAction::Resume => screen = Screen::Playing,
+                    PauseAction::Settings => {
+                        settings_from_pause = true;
+                        screen = Screen::Settings;
+                    }
                     PauseAction::Exit => {
-                        game_over.set_score(world.score());
+                        finish_run(&world, run_board, &levels, current_level, &mut profile, &mut game_over);
                         screen = Screen::GameOver;
                     }
                     PauseAction::None => {},
                 }
             }
+            Screen::Versus => {
+                if let Some(versus) = &versus {
+                    let half_w = screen_w / 2.0;
+                    for (i, side) in versus.worlds.iter().enumerate() {
+                        let area = Rectangle::new(half_w * i as f32, 0.0, half_w, screen_h);
+                        let mut clipped = d.begin_scissor_mode(
+                            area.x as i32,
+                            area.y as i32,
+                            area.width as i32,
+                            area.height as i32,
+                        );
+                        draw_background(
+                            &mut clipped,
+                            assets.texture(TextureId::Background),
+                            area,
+                            side.anger_stage().background_tint(),
+                        );
+                        let view = versus_cameras[i].camera(
+                            side,
+                            area.x,
+                            screen_h,
+                            settings.camera_motion.scale(),
+                        );
+                        let mut shaken = clipped.begin_mode2D(view);
+                        render::draw_world(
+                            &mut shaken,
+                            side,
+                            &assets,
+                            render::DrawOptions {
+                                landing_markers: false,
+                                palette: settings.palette,
+                            },
+                        );
+                    }
+                    d.draw_rectangle(
+                        (half_w - config::VERSUS_DIVIDER_WIDTH / 2.0) as i32,
+                        0,
+                        config::VERSUS_DIVIDER_WIDTH as i32,
+                        screen_h as i32,
+                        config::COLOR_ACCENT_BORDER,
+                    );
+                    if net.as_ref().is_some_and(NetSession::waiting) {
+                        draw_net_waiting(&mut d, screen_w, screen_h, &font);
+                    }
+                }
+            }
+            Screen::Settings => {
+                match settings_menu.update_and_draw(
+                    &mut d,
+                    &mut settings,
+                    screen_w,
+                    screen_h,
+                    mouse,
+                    clicked,
+                    &font,
+                ) {
+                    SettingsAction::Changed => settings_changed = true,
+                    SettingsAction::Back => {
+                        screen = if settings_from_pause {
+                            Screen::Paused
+                        } else {
+                            Screen::Menu
+                        };
+                    }
+                    SettingsAction::None => {}
+                }
+            }
+            Screen::Lobby => {
+                match lobby.update_and_draw(&mut d, screen_w, screen_h, mouse, clicked, &font) {
+                    LobbyAction::Host(kind) => {
+                        let info = MatchInfo {
+                            seed: rng::seed_from_clock(),
+                            kind,
+                            rules: menu.rules(),
+                            mode: menu.mode(),
+                            music_tracks: music.swap_count().min(u8::MAX as usize) as u8,
+                            bucket_size: bucket_size(&assets),
+                            giant_cat_size: assets.atlas().sheet(SpriteId::CryingCat).frame_size(),
+                        };
+                        match NetSession::host(info) {
+                            Ok(session) => {
+                                lobby.set_status(
+                                    &format!("Waiting for a player on port {}...", config::NET_PORT),
+                                    true,
+                                );
+                                net = Some(session);
+                            }
+                            Err(err) => lobby.set_status(&format!("Could not host: {}", err), false),
+                        }
+                    }
+                    LobbyAction::Join(address) => match NetSession::join(&address) {
+                        Ok(session) => {
+                            lobby.set_status(&format!("Connecting to {}...", address), true);
+                            net = Some(session);
+                        }
+                        Err(err) => lobby.set_status(&format!("Could not join: {}", err), false),
+                    },
+                    LobbyAction::Cancel => {
+                        net = None;
+                        lobby.set_status("", false);
+                    }
+                    LobbyAction::Back => {
+                        net = None;
+                        lobby.set_status("", false);
+                        screen = Screen::Menu;
+                    }
+                    LobbyAction::None => {}
+                }
+            }
+            Screen::VersusOver => {
+                match versus_over.update_and_draw(&mut d, screen_w, screen_h, mouse, clicked, &font) {
+                    VersusOverAction::Rematch if net_match => screen = Screen::Lobby,
+                    VersusOverAction::Rematch => {
+                        versus = Some(create_versus(
+                            screen_w,
+                            screen_h,
+                            &assets,
+                            music.swap_count(),
+                            RunSetup::versus(menu.rules(), menu.mode()),
+                        ));
+                        screen = Screen::Versus;
+                    }
+                    VersusOverAction::Menu => {
+                        versus = None;
+                        screen = Screen::Menu;
+                    }
+                    VersusOverAction::None => {}
+                }
+            }
             Screen::GameOver => {
                  d.clear_background(Color::BLACK);
                  /* 
                  render::draw_world(
                     &mut d,
                     &world,
-                    &bucket_texture,
-                    &normal_texture,
-                    &angel_texture,
-                    &devil_texture, 
-                    &crying_cat_texture,
+                    &assets,
                 );
                 */
 
                  match game_over.draw(&mut d, screen_w, screen_h, mouse, clicked, &font) {
                     GameOverAction::Submit => {
-                        leaderboard.reload();
+                        profile.set_name(game_over.name());
+                        if let Some(board) = game_over.board() {
+                            leaderboard.show(board);
+                        }
                         screen = Screen::Leaderboard;
                     },
+                    GameOverAction::Continue => {
+                        screen = if current_level.is_some() {
+                            Screen::LevelSelect
+                        } else {
+                            Screen::Menu
+                        };
+                    }
                     GameOverAction::None => {},
                  }
             }
         }
+        if let Some(title) = music.now_playing() {
+            draw_now_playing(&mut d, title, screen_w, screen_h, &font);
+        }
+        drop(d);
+
+        frame.clear_background(Color::BLACK);
+        // Render textures are stored upside down, hence the negative source height.
+        frame.draw_texture_pro(
+            &target,
+            Rectangle::new(0.0, 0.0, screen_w, -screen_h),
+            viewport.dest(),
+            Vector2::new(0.0, 0.0),
+            0.0,
+            Color::WHITE,
+        );
     }
 }
 
-fn create_world(screen_w: f32, screen_h: f32, bucket_texture: &Texture2D) -> World {
-    let max_angry = rng::get_random_value(config::ANGRY_BAR_MIN_MAX, config::ANGRY_BAR_MAX_MAX);
-    let mut world = World::new(screen_w, screen_h, max_angry);
-    let bucket_frame_w =
-        bucket_texture.width as f32 / config::BUCKET_FRAME_COLS as f32 * config::BUCKET_DRAW_SCALE;
-    let bucket_frame_h = bucket_texture.height as f32 / config::BUCKET_FRAME_ROWS as f32
-        * config::BUCKET_DRAW_SCALE;
-    world.bucket.set_size(Vector2::new(bucket_frame_w, bucket_frame_h), screen_w, screen_h);
-    world.set_base_bucket_size(Vector2::new(bucket_frame_w, bucket_frame_h));
+fn create_world(
+    screen_w: f32,
+    screen_h: f32,
+    assets: &Assets,
+    music_tracks: usize,
+    setup: RunSetup,
+) -> World {
+    let atlas = assets.atlas();
+    let mut world = World::new(screen_w, screen_h, setup);
+    world.set_music_tracks(music_tracks);
+    world.set_giant_cat_size(atlas.sheet(SpriteId::CryingCat).frame_size());
+    world.set_bucket_size(bucket_size(assets), screen_w, screen_h);
+    world.set_frame_times(atlas.frame_times().clone());
     world
 }
+
+/// The bucket hitbox: the bucket sprite at its drawn scale.
+fn bucket_size(assets: &Assets) -> Vector2 {
+    assets.atlas().sheet(SpriteId::Bucket).frame_size() * config::BUCKET_DRAW_SCALE
+}
+
+/// LAN peers simulate with the host's sprite sizes, so one whose sheets differ or are
+/// missing still stays in step.
+fn use_match_sizes(world: &mut World, info: &MatchInfo) {
+    world.set_giant_cat_size(info.giant_cat_size);
+    world.set_bucket_size(info.bucket_size, world.arena_width(), config::SCREEN_H as f32);
+}
+
+/// Carries out a debug console command on the current run and says what happened.
+fn run_command(
+    command: Command,
+    world: &mut World,
+    time_scale: &mut f32,
+    assets: &Assets,
+    music_tracks: usize,
+) -> String {
+    match command {
+        Command::Spawn { kind, x } => {
+            let x = x.unwrap_or(world.arena_width() / 2.0);
+            let speed = config::OBJ_BASE_SPEED * world.difficulty();
+            world.objects.push(FallingObject::new(x, kind, speed));
+            format!("spawned {} at {}", kind.name(), x)
+        }
+        Command::Effect(effect) => {
+            world.receive_devil_effect(effect);
+            format!("applied {}", effect.name())
+        }
+        Command::Angry(points) => {
+            world.angry_points = points.clamp(0, world.max_angry_points);
+            format!("angry {}/{}", world.angry_points, world.max_angry_points)
+        }
+        Command::Seed(seed) => {
+            let setup = RunSetup {
+                seed,
+                ..world.setup().clone()
+            };
+            *world = create_world(
+                config::SCREEN_W as f32,
+                config::SCREEN_H as f32,
+                assets,
+                music_tracks,
+                setup,
+            );
+            format!("restarted with seed {}", seed)
+        }
+        Command::TimeScale(scale) => {
+            *time_scale = scale.clamp(config::DEBUG_TIME_SCALE_MIN, config::DEBUG_TIME_SCALE_MAX);
+            format!("timescale x{}", time_scale)
+        }
+    }
+}
+
+/// Two half-width worlds built from one setup, so both players face the same cats.
+fn create_versus(
+    screen_w: f32,
+    screen_h: f32,
+    assets: &Assets,
+    music_tracks: usize,
+    setup: RunSetup,
+) -> Versus {
+    let arena_w = screen_w / 2.0;
+    Versus::new(
+        create_world(arena_w, screen_h, assets, music_tracks, setup.clone()),
+        create_world(arena_w, screen_h, assets, music_tracks, setup),
+    )
+}
+
+/// Shown over a LAN match while the other player's inputs are late.
+fn draw_net_waiting(d: &mut RaylibDrawHandle, screen_w: f32, screen_h: f32, font: &WeakFont) {
+    let text = "Waiting for the other player...";
+    let size = 24.0;
+    let text_w = font.measure_text(text, size, 1.0).x;
+    d.draw_rectangle(0, 0, screen_w as i32, screen_h as i32, Color::new(0, 0, 0, 120));
+    d.draw_text_ex(
+        font,
+        text,
+        Vector2::new((screen_w - text_w) / 2.0, screen_h / 2.0 - size / 2.0),
+        size,
+        1.0,
+        Color::WHITE,
+    );
+}
+
+/// Names the track that just started, in the bottom-left corner.
+fn draw_now_playing(d: &mut RaylibDrawHandle, title: &str, screen_w: f32, screen_h: f32, font: &WeakFont) {
+    let text = format!("Now playing: {}", title);
+    let size = 18.0;
+    let text_w = font.measure_text(&text, size, 1.0).x;
+    let rect = Rectangle::new(8.0, screen_h - size - 20.0, (text_w + 20.0).min(screen_w - 16.0), size + 12.0);
+    d.draw_rectangle_rec(rect, Color::new(0, 0, 0, 140));
+    d.draw_text_ex(
+        font,
+        &text,
+        Vector2::new(rect.x + 10.0, rect.y + 6.0),
+        size,
+        1.0,
+        Color::WHITE,
+    );
+}
+
+/// Covers `area` with the background, cropping it to keep its aspect ratio.
+fn draw_background(d: &mut RaylibDrawHandle, texture: &Texture2D, area: Rectangle, tint: Color) {
+    let scale = (area.width / texture.width as f32).max(area.height / texture.height as f32);
+    let dest_w = texture.width as f32 * scale;
+    let dest_h = texture.height as f32 * scale;
+    d.draw_texture_pro(
+        texture,
+        Rectangle::new(0.0, 0.0, texture.width as f32, texture.height as f32),
+        Rectangle::new(
+            area.x + (area.width - dest_w) / 2.0,
+            area.y + (area.height - dest_h) / 2.0,
+            dest_w,
+            dest_h,
+        ),
+        Vector2::new(0.0, 0.0),
+        0.0,
+        tint,
+    );
+}
+
+/// Hands the finished run to the game over screen and banks any campaign stars, unless
+/// debug tools touched the run.
+fn finish_run(
+    world: &World,
+    board: Result<Board, Unranked>,
+    levels: &[Level],
+    current_level: Option<usize>,
+    profile: &mut Profile,
+    game_over: &mut GameOverState,
+) {
+    let result = world.result();
+    if let (Some(stars), Some(level)) = (result.stars, current_level.and_then(|i| levels.get(i)))
+        && board != Err(Unranked::Debug)
+    {
+        profile.record_stars(&level.id, stars);
+    }
+    game_over.set_result(result, board, &profile.name);
+}
@@ -0,0 +1,382 @@
+use std::borrow::Cow;
+use std::env;
+use std::ffi::CString;
+use std::fs;
+use std::io;
+use std::marker::PhantomData;
+use std::path::{Path, PathBuf};
+use raylib::ffi;
+use raylib::prelude::*;
+use crate::config;
+use crate::render::atlas::{Atlas, SheetDef, SpriteId};
+
+/// The standalone textures, by what they are rather than where they live. Sprites are
+/// packed into the atlas instead.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum TextureId {
+    Logo,
+    Background,
+}
+
+impl TextureId {
+    pub const ALL: [TextureId; 2] = [TextureId::Logo, TextureId::Background];
+
+    /// Path under the assets root.
+    pub fn file(self) -> &'static str {
+        match self {
+            TextureId::Logo => "UI/bucket-logo.png",
+            TextureId::Background => "UI/play_background.png",
+        }
+    }
+}
+
+/// The loaded textures plus the folder everything else is read from. Nothing in here
+/// panics on a missing file: textures fall back to a checkerboard and the audio loaders
+/// leave missing tracks silent. Built with `embed-assets`, files are read out of the
+/// executable first and only fall back to the folder if they were not embedded.
+pub struct Assets {
+    root: PathBuf,
+    textures: Vec<Texture2D>,
+    atlas: Option<Atlas>,
+    /// The current run's background; levels can swap it out.
+    background: Option<Texture2D>,
+}
+
+impl Assets {
+    pub fn load(rl: &mut RaylibHandle, thread: &RaylibThread) -> Self {
+        let mut assets = Self {
+            root: resolve_root(),
+            textures: Vec::new(),
+            atlas: None,
+            background: None,
+        };
+        assets.textures = TextureId::ALL
+            .iter()
+            .map(|id| {
+                assets.load_texture(rl, thread, id.file()).unwrap_or_else(|err| {
+                    eprintln!("warning: texture {}: {}", id.file(), err);
+                    placeholder_texture(rl, thread)
+                })
+            })
+            .collect();
+        let sprites = SpriteId::ALL
+            .iter()
+            .map(|id| {
+                let (image, sheet) = match assets.load_image(id.file()) {
+                    Ok(image) => (image, assets.load_sheet(*id)),
+                    Err(err) => {
+                        eprintln!("warning: texture {}: {}", id.file(), err);
+                        (placeholder_image(), None)
+                    }
+                };
+                let sheet = sheet.unwrap_or_else(|| SheetDef::single_frame(image.width(), image.height()));
+                (image, sheet)
+            })
+            .collect();
+        assets.atlas = Some(Atlas::build(rl, thread, sprites).expect("upload sprite atlas"));
+        assets
+    }
+
+    pub fn atlas(&self) -> &Atlas {
+        self.atlas.as_ref().expect("atlas is built in Assets::load")
+    }
+
+    pub fn texture(&self, id: TextureId) -> &Texture2D {
+        if id == TextureId::Background
+            && let Some(background) = &self.background
+        {
+            return background;
+        }
+        let idx = TextureId::ALL.iter().position(|other| *other == id).unwrap_or(0);
+        &self.textures[idx]
+    }
+
+    /// Resolves a path relative to the assets root.
+    pub fn path(&self, relative: &str) -> PathBuf {
+        self.root.join(relative)
+    }
+
+    pub fn read(&self, relative: &str) -> io::Result<Cow<'static, [u8]>> {
+        if let Some(bytes) = embedded::get(relative) {
+            return Ok(Cow::Borrowed(bytes));
+        }
+        fs::read(self.path(relative)).map(Cow::Owned)
+    }
+
+    pub fn read_to_string(&self, relative: &str) -> io::Result<String> {
+        let bytes = self.read(relative)?.into_owned();
+        String::from_utf8(bytes).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+    }
+
+    /// File names directly inside `dir`, sorted.
+    pub fn list(&self, dir: &str) -> io::Result<Vec<String>> {
+        let mut names = embedded::list(dir);
+        if names.is_empty() {
+            names = fs::read_dir(self.path(dir))?
+                .filter_map(|entry| entry.ok())
+                .map(|entry| entry.file_name().to_string_lossy().into_owned())
+                .collect();
+        }
+        names.sort();
+        Ok(names)
+    }
+
+    pub fn load_image(&self, relative: &str) -> Result<Image, String> {
+        let bytes = self.read(relative).map_err(|err| err.to_string())?;
+        Image::load_image_from_mem(&file_type(relative), &bytes).map_err(|err| err.to_string())
+    }
+
+    /// `None` (with a warning) if the sheet is missing or does not parse.
+    fn load_sheet(&self, id: SpriteId) -> Option<SheetDef> {
+        let file = id.sheet_file();
+        let mut sheet: SheetDef = self
+            .read_to_string(&file)
+            .map_err(|err| err.to_string())
+            .and_then(|text| ron::from_str(&text).map_err(|err| err.to_string()))
+            .map_err(|err| eprintln!("warning: sprite sheet {}: {}", file, err))
+            .ok()?;
+        for name in sheet.drop_bad_durations() {
+            eprintln!("warning: sprite sheet {}: ignoring durations of \"{}\"", file, name);
+        }
+        Some(sheet)
+    }
+
+    pub fn load_texture(
+        &self,
+        rl: &mut RaylibHandle,
+        thread: &RaylibThread,
+        relative: &str,
+    ) -> Result<Texture2D, String> {
+        if let Some(bytes) = embedded::get(relative) {
+            let image = Image::load_image_from_mem(&file_type(relative), bytes)
+                .map_err(|err| err.to_string())?;
+            return rl
+                .load_texture_from_image(thread, &image)
+                .map_err(|err| err.to_string());
+        }
+        rl.load_texture(thread, &self.path(relative).to_string_lossy())
+            .map_err(|err| err.to_string())
+    }
+
+    /// Streams from disk, or straight from the embedded bytes, which live as long as the
+    /// program does.
+    pub fn load_music<'a>(&self, audio: &'a RaylibAudio, relative: &str) -> Result<LoadedMusic<'a>, String> {
+        if let Some(bytes) = embedded::get(relative) {
+            // raylib's safe loader wants an owned copy; the embedded bytes never move.
+            let file_type = CString::new(file_type(relative)).map_err(|err| err.to_string())?;
+            let raw = unsafe {
+                ffi::LoadMusicStreamFromMemory(file_type.as_ptr(), bytes.as_ptr(), bytes.len() as i32)
+            };
+            if raw.stream.buffer.is_null() {
+                return Err("could not decode the track".to_string());
+            }
+            return Ok(LoadedMusic::new(raw));
+        }
+        let path = self.path(relative);
+        if !path.is_file() {
+            return Err("file is missing".to_string());
+        }
+        let music = audio
+            .new_music(&path.to_string_lossy())
+            .map_err(|err| err.to_string())?;
+        Ok(LoadedMusic::new(unsafe { music.unwrap() }))
+    }
+
+    pub fn load_wave<'a>(&self, audio: &'a RaylibAudio, relative: &str) -> Result<Wave<'a>, String> {
+        let bytes = self.read(relative).map_err(|err| err.to_string())?;
+        audio
+            .new_wave_from_memory(&file_type(relative), &bytes)
+            .map_err(|err| err.to_string())
+    }
+
+    /// Swaps in a level's background from `UI/`, or goes back to the default for `None`
+    /// or a file that will not load.
+    pub fn set_background(&mut self, rl: &mut RaylibHandle, thread: &RaylibThread, file: Option<&str>) {
+        let background = file.and_then(|file| {
+            self.load_texture(rl, thread, &format!("UI/{}", file))
+                .map_err(|err| eprintln!("warning: level background {}: {}", file, err))
+                .ok()
+        });
+        self.background = background;
+    }
+}
+
+/// A music stream, held raw so embedded tracks can play from their static bytes without
+/// a copy. Unloaded on drop.
+pub struct LoadedMusic<'a> {
+    raw: ffi::Music,
+    _audio: PhantomData<&'a RaylibAudio>,
+}
+
+impl LoadedMusic<'_> {
+    fn new(raw: ffi::Music) -> Self {
+        Self {
+            raw,
+            _audio: PhantomData,
+        }
+    }
+
+    pub fn stream(&self) -> ffi::AudioStream {
+        self.raw.stream
+    }
+
+    pub fn play_stream(&mut self) {
+        unsafe { ffi::PlayMusicStream(self.raw) }
+    }
+
+    pub fn resume_stream(&mut self) {
+        unsafe { ffi::ResumeMusicStream(self.raw) }
+    }
+
+    pub fn pause_stream(&mut self) {
+        unsafe { ffi::PauseMusicStream(self.raw) }
+    }
+
+    pub fn stop_stream(&mut self) {
+        unsafe { ffi::StopMusicStream(self.raw) }
+    }
+
+    pub fn update_stream(&mut self) {
+        unsafe { ffi::UpdateMusicStream(self.raw) }
+    }
+
+    pub fn set_volume(&mut self, volume: f32) {
+        unsafe { ffi::SetMusicVolume(self.raw, volume) }
+    }
+
+    pub fn set_pitch(&mut self, pitch: f32) {
+        unsafe { ffi::SetMusicPitch(self.raw, pitch) }
+    }
+}
+
+impl Drop for LoadedMusic<'_> {
+    fn drop(&mut self) {
+        unsafe { ffi::UnloadMusicStream(self.raw) }
+    }
+}
+
+/// raylib's "file type" for the load-from-memory calls, e.g. `.png`.
+fn file_type(relative: &str) -> String {
+    let ext = Path::new(relative)
+        .extension()
+        .map(|ext| ext.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    format!(".{}", ext)
+}
+
+/// Where the asset files live: `--assets <dir>` on the command line, then the
+/// `BUCKET_CAT_ASSETS` variable, then an `assets` folder next to the executable, then
+/// `src/assets` under the working directory for `cargo run`.
+fn resolve_root() -> PathBuf {
+    let mut args = env::args().skip(1);
+    let mut from_flag = None;
+    while let Some(arg) = args.next() {
+        if arg == config::ASSETS_FLAG {
+            from_flag = args.next();
+        } else if let Some(dir) = arg.strip_prefix(&format!("{}=", config::ASSETS_FLAG)) {
+            from_flag = Some(dir.to_string());
+        }
+    }
+    let requested = from_flag.or_else(|| env::var(config::ASSETS_ENV).ok());
+    if let Some(dir) = requested {
+        if Path::new(&dir).is_dir() {
+            return PathBuf::from(dir);
+        }
+        eprintln!("warning: assets folder {} not found", dir);
+    }
+
+    let beside_exe = env::current_exe()
+        .ok()
+        .and_then(|exe| Some(exe.parent()?.join(config::ASSETS_DIR_NAME)));
+    if let Some(dir) = beside_exe.filter(|dir| dir.is_dir()) {
+        return dir;
+    }
+    let dev = PathBuf::from(config::ASSETS_DEV_DIR);
+    if !dev.is_dir() && !cfg!(feature = "embed-assets") {
+        eprintln!("warning: no assets folder found; using placeholders");
+    }
+    dev
+}
+
+fn placeholder_image() -> Image {
+    let size = config::PLACEHOLDER_SIZE;
+    let checks = config::PLACEHOLDER_CHECKS;
+    Image::gen_image_checked(
+        size,
+        size,
+        size / checks,
+        size / checks,
+        config::COLOR_PLACEHOLDER_A,
+        config::COLOR_PLACEHOLDER_B,
+    )
+}
+
+fn placeholder_texture(rl: &mut RaylibHandle, thread: &RaylibThread) -> Texture2D {
+    rl.load_texture_from_image(thread, &placeholder_image())
+        .expect("upload placeholder texture")
+}
+
+#[cfg(feature = "embed-assets")]
+mod embedded {
+    macro_rules! embed {
+        ($($file:literal),* $(,)?) => {
+            &[$(($file, include_bytes!(concat!("assets/", $file)))),*]
+        };
+    }
+
+    /// Everything the game loads, compiled in.
+    const FILES: &[(&str, &[u8])] = embed![
+        "cat/bucket.png",
+        "cat/bucket.ron",
+        "cat/normalneko.png",
+        "cat/normalneko.ron",
+        "cat/angelneko.png",
+        "cat/angelneko.ron",
+        "cat/devilneko.png",
+        "cat/devilneko.ron",
+        "cat/crying_cat.png",
+        "cat/crying_cat.ron",
+        "UI/bucket-logo.png",
+        "UI/play_background.png",
+        "sound_effects/playlist.ron",
+        "sound_effects/sakura-default-music.mp3",
+        "sound_effects/sakura-tension-layer.wav",
+        "sound_effects/chipi_chapa.mp3",
+        "sound_effects/happy_cat.mp3",
+        "sound_effects/maxwell_cat.mp3",
+        "sound_effects/rickroll.mp3",
+        "sound_effects/uiia_cat.mp3",
+        "levels/01_first_steps.ron",
+        "levels/02_angel_wings.ron",
+        "levels/03_devils_dance.ron",
+        "levels/04_golden_rain.ron",
+    ];
+
+    pub fn get(relative: &str) -> Option<&'static [u8]> {
+        FILES
+            .iter()
+            .find(|(file, _)| *file == relative)
+            .map(|(_, bytes)| *bytes)
+    }
+
+    pub fn list(dir: &str) -> Vec<String> {
+        let prefix = format!("{}/", dir.trim_end_matches('/'));
+        FILES
+            .iter()
+            .filter_map(|(file, _)| file.strip_prefix(&prefix))
+            .filter(|name| !name.contains('/'))
+            .map(str::to_string)
+            .collect()
+    }
+}
+
+#[cfg(not(feature = "embed-assets"))]
+mod embedded {
+    pub fn get(_relative: &str) -> Option<&'static [u8]> {
+        None
+    }
+
+    pub fn list(_dir: &str) -> Vec<String> {
+        Vec::new()
+    }
+}
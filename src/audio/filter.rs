@@ -0,0 +1,73 @@
+use std::os::raw::{c_uint, c_void};
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicU32, Ordering};
+use raylib::ffi;
+use crate::assets::LoadedMusic;
+use crate::config;
+
+// raylib hands stream processors interleaved f32 frames in the mixer's stereo format.
+const CHANNELS: usize = 2;
+
+/// Which part of a track a stream is. A track and its layer play at the same time, so
+/// each filters with its own history.
+#[derive(Clone, Copy)]
+pub enum Part {
+    Track,
+    Layer,
+}
+
+type Processor = unsafe extern "C" fn(*mut c_void, c_uint);
+
+static AMOUNT: AtomicU32 = AtomicU32::new(0);
+static HISTORY: Mutex<[[f32; CHANNELS]; 2]> = Mutex::new([[0.0; CHANNELS]; 2]);
+
+/// Hooks the low-pass into a music stream. It passes audio through untouched until
+/// `set_amount` raises it above zero.
+pub fn attach(music: &LoadedMusic, part: Part) {
+    unsafe { ffi::AttachAudioStreamProcessor(music.stream(), Some(processor(part))) }
+}
+
+pub fn detach(music: &LoadedMusic, part: Part) {
+    unsafe { ffi::DetachAudioStreamProcessor(music.stream(), Some(processor(part))) }
+}
+
+// raylib does not say which stream a buffer came from, so each part gets its own callback.
+fn processor(part: Part) -> Processor {
+    match part {
+        Part::Track => process::<0>,
+        Part::Layer => process::<1>,
+    }
+}
+
+/// 0.0 leaves the music dry, 1.0 muffles it down to `MUSIC_MUFFLE_CUTOFF`.
+pub fn set_amount(amount: f32) {
+    AMOUNT.store(amount.clamp(0.0, 1.0).to_bits(), Ordering::Relaxed);
+}
+
+/// One-pole low-pass, run on the audio thread. Tracks share a history, which is fine
+/// because only the current track keeps playing while the filter is on; its layer has
+/// the other one.
+unsafe extern "C" fn process<const PART: usize>(buffer: *mut c_void, frames: c_uint) {
+    let amount = f32::from_bits(AMOUNT.load(Ordering::Relaxed));
+    let Ok(mut histories) = HISTORY.try_lock() else {
+        return;
+    };
+    let history = &mut histories[PART];
+    let samples =
+        unsafe { std::slice::from_raw_parts_mut(buffer as *mut f32, frames as usize * CHANNELS) };
+    if amount <= 0.0 {
+        // Track the dry signal so switching the filter on does not click.
+        if let Some(frame) = samples.chunks_exact(CHANNELS).last() {
+            history.copy_from_slice(frame);
+        }
+        return;
+    }
+    let muffled = (std::f32::consts::TAU * config::MUSIC_MUFFLE_CUTOFF / config::AUDIO_MIX_RATE).min(1.0);
+    let alpha = 1.0 + (muffled - 1.0) * amount;
+    for frame in samples.chunks_exact_mut(CHANNELS) {
+        for (sample, last) in frame.iter_mut().zip(history.iter_mut()) {
+            *last += alpha * (*sample - *last);
+            *sample = *last;
+        }
+    }
+}
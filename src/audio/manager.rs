@@ -0,0 +1,357 @@
+use raylib::prelude::*;
+use crate::assets::{Assets, LoadedMusic};
+use crate::audio::filter::{self, Part};
+use crate::audio::playlist::{Playlist, TrackRole, TrackEntry};
+use crate::config;
+use crate::game::effects::MusicMode;
+
+struct Channel<'a> {
+    music: LoadedMusic<'a>,
+    /// Intensity stem kept in step with `music`, mixed in by the manager's intensity.
+    layer: Option<LoadedMusic<'a>>,
+    title: String,
+    gain: f32,
+    started: bool,
+    playing: bool,
+}
+
+impl<'a> Channel<'a> {
+    fn new(music: LoadedMusic<'a>, layer: Option<LoadedMusic<'a>>, title: &str) -> Self {
+        filter::attach(&music, Part::Track);
+        if let Some(layer) = &layer {
+            filter::attach(layer, Part::Layer);
+        }
+        Self {
+            music,
+            layer,
+            title: title.to_string(),
+            gain: 0.0,
+            started: false,
+            playing: false,
+        }
+    }
+
+    /// Picks up where the track left off if it has been played before.
+    fn start(&mut self) {
+        if self.playing {
+            return;
+        }
+        let started = self.started;
+        for music in self.streams_mut() {
+            if started {
+                music.resume_stream();
+            } else {
+                music.play_stream();
+            }
+        }
+        self.started = true;
+        self.playing = true;
+    }
+
+    fn pause(&mut self) {
+        if self.playing {
+            for music in self.streams_mut() {
+                music.pause_stream();
+            }
+            self.playing = false;
+        }
+    }
+
+    fn stop(&mut self) {
+        if self.started {
+            for music in self.streams_mut() {
+                music.stop_stream();
+            }
+        }
+        self.started = false;
+        self.playing = false;
+        self.gain = 0.0;
+    }
+
+    fn streams_mut(&mut self) -> impl Iterator<Item = &mut LoadedMusic<'a>> {
+        std::iter::once(&mut self.music).chain(self.layer.as_mut())
+    }
+}
+
+impl Drop for Channel<'_> {
+    fn drop(&mut self) {
+        filter::detach(&self.music, Part::Track);
+        if let Some(layer) = &self.layer {
+            filter::detach(layer, Part::Layer);
+        }
+    }
+}
+
+/// Owns every music stream. The app states which track it wants each frame with `play`,
+/// `pause` or `stop`; switching tracks crossfades, and a track that faded out keeps its
+/// position for when it comes back. While paused the current track plays on muffled.
+pub struct AudioManager<'a> {
+    audio: &'a RaylibAudio,
+    default_entry: Option<TrackEntry>,
+    default: Option<Channel<'a>>,
+    boss: Option<Channel<'a>>,
+    menu: Option<Channel<'a>>,
+    alternates: Vec<Channel<'a>>,
+    current: Option<MusicMode>,
+    title_timer: f32,
+    intensity: f32,
+    layer_gain: f32,
+    muffle: f32,
+    paused: bool,
+    volume: f32,
+    pitch: f32,
+    duck_timer: f32,
+    duck_gain: f32,
+}
+
+impl<'a> AudioManager<'a> {
+    /// Loads every track in the playlist. Tracks that fail to load are left out with a
+    /// warning; a role with no track just stays silent.
+    pub fn load(audio: &'a RaylibAudio, assets: &Assets, playlist: &Playlist) -> Self {
+        let first = |role| {
+            playlist
+                .with_role(role)
+                .find_map(|entry| load_entry(audio, assets, entry))
+        };
+        let alternates = playlist
+            .with_role(TrackRole::Swap)
+            .filter_map(|entry| load_entry(audio, assets, entry))
+            .collect();
+        Self {
+            audio,
+            default_entry: playlist.with_role(TrackRole::Default).next().cloned(),
+            default: first(TrackRole::Default),
+            boss: first(TrackRole::Boss),
+            menu: first(TrackRole::Menu),
+            alternates,
+            current: None,
+            title_timer: 0.0,
+            intensity: 0.0,
+            layer_gain: 0.0,
+            muffle: 0.0,
+            paused: false,
+            volume: 1.0,
+            pitch: 1.0,
+            duck_timer: 0.0,
+            duck_gain: 1.0,
+        }
+    }
+
+    /// How many devil-swap tracks actually loaded.
+    pub fn swap_count(&self) -> usize {
+        self.alternates.len()
+    }
+
+    /// Uses a level's own track as the default, or the playlist's default for `None`
+    /// or a file that will not load.
+    pub fn use_default_track(&mut self, assets: &Assets, file: Option<&str>) {
+        let level_track = file.and_then(|file| load_channel(self.audio, assets, file, None, file));
+        let channel =
+            level_track.or_else(|| load_entry(self.audio, assets, self.default_entry.as_ref()?));
+        if let Some(old) = &mut self.default {
+            old.stop();
+        }
+        self.default = channel;
+        if self.current == Some(MusicMode::Default) {
+            self.current = None;
+        }
+    }
+
+    /// Title of the track that just started, for a few seconds after it starts.
+    pub fn now_playing(&self) -> Option<&str> {
+        if self.title_timer <= 0.0 || self.paused {
+            return None;
+        }
+        let track = self.current?;
+        let channel = match track {
+            MusicMode::Default => self.default.as_ref(),
+            MusicMode::Boss => self.boss.as_ref(),
+            MusicMode::Menu => self.menu.as_ref(),
+            MusicMode::Alternate(idx) => self.alternates.get(idx),
+        }?;
+        Some(&channel.title)
+    }
+
+    pub fn play(&mut self, track: MusicMode) {
+        let track = self.normalize(track);
+        if self.paused {
+            self.paused = false;
+            for channel in self.channels_mut() {
+                if channel.started && channel.gain > 0.0 {
+                    channel.start();
+                }
+            }
+        }
+        if self.current == Some(track) {
+            return;
+        }
+        self.current = Some(track);
+        if let Some(channel) = self.channel_mut(track) {
+            channel.start();
+            self.title_timer = config::MUSIC_TITLE_TIME;
+        }
+    }
+
+    /// Muffles the current track and holds any that were fading, to carry on after `play`.
+    pub fn pause(&mut self) {
+        if self.paused {
+            return;
+        }
+        self.paused = true;
+        let current = self.current;
+        for (track, channel) in self.tracks_mut() {
+            if current != Some(track) {
+                channel.pause();
+            }
+        }
+    }
+
+    /// Silences everything and rewinds, ready for the next run.
+    pub fn stop(&mut self) {
+        if self.current.is_none() && !self.paused {
+            return;
+        }
+        self.current = None;
+        self.paused = false;
+        for channel in self.channels_mut() {
+            channel.stop();
+        }
+    }
+
+    pub fn set_volume(&mut self, volume: f32) {
+        self.volume = volume;
+    }
+
+    pub fn set_pitch(&mut self, pitch: f32) {
+        self.pitch = pitch;
+    }
+
+    /// How hard the intensity stems come in, from 0.0 to 1.0.
+    pub fn set_intensity(&mut self, intensity: f32) {
+        self.intensity = intensity.clamp(0.0, 1.0);
+    }
+
+    /// Lowers the music for `seconds` so an important sound effect stands out.
+    pub fn duck(&mut self, seconds: f32) {
+        self.duck_timer = self.duck_timer.max(seconds);
+    }
+
+    pub fn update(&mut self, dt: f32) {
+        let muffle_target = if self.paused { 1.0 } else { 0.0 };
+        self.muffle = approach(self.muffle, muffle_target, dt / config::MUSIC_MUFFLE_FADE);
+        filter::set_amount(self.muffle);
+        if !self.paused {
+            self.layer_gain = approach(self.layer_gain, self.intensity, dt / config::MUSIC_LAYER_FADE);
+        }
+
+        let fade_step = if config::MUSIC_CROSSFADE > 0.0 {
+            dt / config::MUSIC_CROSSFADE
+        } else {
+            1.0
+        };
+
+        self.title_timer = (self.title_timer - dt).max(0.0);
+        self.duck_timer = (self.duck_timer - dt).max(0.0);
+        let duck_target = if self.duck_timer > 0.0 {
+            config::MUSIC_DUCK_LEVEL
+        } else {
+            1.0
+        };
+        let duck_step = dt / config::MUSIC_DUCK_FADE;
+        self.duck_gain = approach(self.duck_gain, duck_target, duck_step);
+
+        let current = self.current;
+        let level = self.volume * self.duck_gain;
+        let pitch = self.pitch;
+        let layer_gain = self.layer_gain;
+        for (track, channel) in self.tracks_mut() {
+            let target = if current == Some(track) { 1.0 } else { 0.0 };
+            channel.gain = approach(channel.gain, target, fade_step);
+            if !channel.playing {
+                continue;
+            }
+            if channel.gain <= 0.0 && target == 0.0 {
+                channel.pause();
+                continue;
+            }
+            channel.music.set_volume(channel.gain * level);
+            if let Some(layer) = &mut channel.layer {
+                layer.set_volume(channel.gain * level * layer_gain);
+            }
+            for music in channel.streams_mut() {
+                music.set_pitch(pitch);
+                music.update_stream();
+            }
+        }
+    }
+
+    fn channel_mut(&mut self, track: MusicMode) -> Option<&mut Channel<'a>> {
+        match track {
+            MusicMode::Default => self.default.as_mut(),
+            MusicMode::Boss => self.boss.as_mut(),
+            MusicMode::Menu => self.menu.as_mut(),
+            MusicMode::Alternate(idx) => self.alternates.get_mut(idx),
+        }
+    }
+
+    /// Out-of-range alternates wrap around onto the loaded ones.
+    fn normalize(&self, track: MusicMode) -> MusicMode {
+        match track {
+            MusicMode::Alternate(idx) => MusicMode::Alternate(idx % self.alternates.len().max(1)),
+            other => other,
+        }
+    }
+
+    fn tracks_mut(&mut self) -> impl Iterator<Item = (MusicMode, &mut Channel<'a>)> {
+        [
+            (MusicMode::Default, self.default.as_mut()),
+            (MusicMode::Boss, self.boss.as_mut()),
+            (MusicMode::Menu, self.menu.as_mut()),
+        ]
+        .into_iter()
+        .filter_map(|(track, channel)| Some((track, channel?)))
+        .chain(
+            self.alternates
+                .iter_mut()
+                .enumerate()
+                .map(|(i, channel)| (MusicMode::Alternate(i), channel)),
+        )
+    }
+
+    fn channels_mut(&mut self) -> impl Iterator<Item = &mut Channel<'a>> {
+        self.tracks_mut().map(|(_, channel)| channel)
+    }
+}
+
+fn approach(value: f32, target: f32, step: f32) -> f32 {
+    if value < target {
+        (value + step).min(target)
+    } else {
+        (value - step).max(target)
+    }
+}
+
+fn load_entry<'a>(audio: &'a RaylibAudio, assets: &Assets, entry: &TrackEntry) -> Option<Channel<'a>> {
+    load_channel(audio, assets, &entry.file, entry.layer.as_deref(), &entry.title)
+}
+
+/// A missing or unreadable layer only loses the layer; the track still plays.
+fn load_channel<'a>(
+    audio: &'a RaylibAudio,
+    assets: &Assets,
+    file: &str,
+    layer: Option<&str>,
+    title: &str,
+) -> Option<Channel<'a>> {
+    let music = load_stream(audio, assets, file)?;
+    let layer = layer.and_then(|layer| load_stream(audio, assets, layer));
+    Some(Channel::new(music, layer, title))
+}
+
+fn load_stream<'a>(audio: &'a RaylibAudio, assets: &Assets, file: &str) -> Option<LoadedMusic<'a>> {
+    let path = format!("{}/{}", config::MUSIC_DIR, file);
+    assets
+        .load_music(audio, &path)
+        .map_err(|err| eprintln!("warning: music track {}: {}", path, err))
+        .ok()
+}
@@ -0,0 +1,4 @@
+pub mod filter;
+pub mod manager;
+pub mod sfx;
+pub mod playlist;
@@ -0,0 +1,45 @@
+use serde::Deserialize;
+use crate::assets::Assets;
+use crate::config;
+
+#[derive(Clone, Copy, PartialEq, Eq, Deserialize)]
+pub enum TrackRole {
+    Default,
+    Swap,
+    Menu,
+    Boss,
+}
+
+#[derive(Clone, Deserialize)]
+pub struct TrackEntry {
+    pub file: String,
+    pub title: String,
+    pub roles: Vec<TrackRole>,
+    /// A stem faded in on top as the game gets more intense. It loops on its own, so one
+    /// that follows the beat must be the same length as `file`.
+    #[serde(default)]
+    pub layer: Option<String>,
+}
+
+/// The music manifest: every track with its title and the roles it can fill.
+pub struct Playlist {
+    pub tracks: Vec<TrackEntry>,
+}
+
+impl Playlist {
+    pub fn load(assets: &Assets) -> Self {
+        let tracks = assets
+            .read_to_string(config::PLAYLIST_FILE)
+            .map_err(|err| err.to_string())
+            .and_then(|text| ron::from_str(&text).map_err(|err| err.to_string()))
+            .unwrap_or_else(|err| {
+                eprintln!("warning: playlist {}: {}", config::PLAYLIST_FILE, err);
+                Vec::new()
+            });
+        Self { tracks }
+    }
+
+    pub fn with_role(&self, role: TrackRole) -> impl Iterator<Item = &TrackEntry> {
+        self.tracks.iter().filter(move |track| track.roles.contains(&role))
+    }
+}
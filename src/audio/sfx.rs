@@ -0,0 +1,272 @@
+use std::collections::HashMap;
+use raylib::prelude::*;
+use crate::assets::Assets;
+use crate::config;
+use crate::game::effects::DevilEffect;
+use crate::game::events::GameEvent;
+use crate::game::objects::CatKind;
+use crate::rng::{self, Rng};
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Sfx {
+    Catch,
+    CatchAngel,
+    CatchGolden,
+    CatchDevil,
+    Bomb,
+    Miss,
+    DevilEffect,
+    Explode,
+    GiantCat,
+    Click,
+}
+
+/// Shape of a synthesized fallback sound.
+#[derive(Clone, Copy)]
+enum Waveform {
+    Sine,
+    Square,
+    Noise,
+}
+
+/// A short sweep from `from` to `to` Hz, used when no sound file exists for an effect.
+#[derive(Clone, Copy)]
+struct Tone {
+    wave: Waveform,
+    from: f32,
+    to: f32,
+    seconds: f32,
+}
+
+impl Sfx {
+    const ALL: [Sfx; 10] = [
+        Sfx::Catch,
+        Sfx::CatchAngel,
+        Sfx::CatchGolden,
+        Sfx::CatchDevil,
+        Sfx::Bomb,
+        Sfx::Miss,
+        Sfx::DevilEffect,
+        Sfx::Explode,
+        Sfx::GiantCat,
+        Sfx::Click,
+    ];
+
+    pub fn for_event(event: GameEvent) -> Option<Sfx> {
+        let sfx = match event {
+            GameEvent::Caught(CatKind::Angel | CatKind::Heart) => Sfx::CatchAngel,
+            GameEvent::Caught(CatKind::Golden) => Sfx::CatchGolden,
+            GameEvent::Caught(CatKind::Devil | CatKind::Explode) => Sfx::CatchDevil,
+            GameEvent::Caught(CatKind::Bomb) => Sfx::Bomb,
+            GameEvent::Caught(_) => Sfx::Catch,
+            // Letting devils and bombs fall is the right call, so it stays quiet.
+            GameEvent::Missed(CatKind::Devil | CatKind::Explode | CatKind::Bomb) => return None,
+            GameEvent::Missed(_) => Sfx::Miss,
+            // The explosion has its own event and sound.
+            GameEvent::DevilEffect(DevilEffect::BucketExplode) => return None,
+            GameEvent::DevilEffect(_) => Sfx::DevilEffect,
+            GameEvent::BucketExploded => Sfx::Explode,
+            GameEvent::GiantCatLanded => Sfx::GiantCat,
+        };
+        Some(sfx)
+    }
+
+    /// File name prefix under `SFX_DIR`; `catch.wav`, `catch_2.wav`, ... all become variants.
+    fn name(self) -> &'static str {
+        match self {
+            Sfx::Catch => "catch",
+            Sfx::CatchAngel => "catch_angel",
+            Sfx::CatchGolden => "catch_golden",
+            Sfx::CatchDevil => "catch_devil",
+            Sfx::Bomb => "bomb",
+            Sfx::Miss => "miss",
+            Sfx::DevilEffect => "devil_effect",
+            Sfx::Explode => "explode",
+            Sfx::GiantCat => "giant_cat",
+            Sfx::Click => "click",
+        }
+    }
+
+    /// Big moments lower the music for a moment so they are heard.
+    pub fn ducks_music(self) -> bool {
+        matches!(self, Sfx::Explode | Sfx::GiantCat | Sfx::DevilEffect)
+    }
+
+    /// How many copies of this effect may overlap.
+    fn max_voices(self) -> usize {
+        match self {
+            Sfx::Catch | Sfx::Miss | Sfx::Click => 4,
+            Sfx::GiantCat => 1,
+            _ => 2,
+        }
+    }
+
+    fn tones(self) -> Vec<Tone> {
+        let tone = |wave, from, to, seconds| Tone { wave, from, to, seconds };
+        match self {
+            Sfx::Catch => vec![
+                tone(Waveform::Sine, 660.0, 990.0, 0.08),
+                tone(Waveform::Sine, 740.0, 1110.0, 0.08),
+                tone(Waveform::Sine, 590.0, 880.0, 0.08),
+            ],
+            Sfx::CatchAngel => vec![
+                tone(Waveform::Sine, 880.0, 1320.0, 0.16),
+                tone(Waveform::Sine, 990.0, 1480.0, 0.16),
+            ],
+            Sfx::CatchGolden => vec![tone(Waveform::Square, 990.0, 1760.0, 0.18)],
+            Sfx::CatchDevil => vec![
+                tone(Waveform::Square, 300.0, 180.0, 0.15),
+                tone(Waveform::Square, 260.0, 150.0, 0.15),
+            ],
+            Sfx::Bomb => vec![tone(Waveform::Noise, 0.0, 0.0, 0.35)],
+            Sfx::Miss => vec![
+                tone(Waveform::Sine, 440.0, 220.0, 0.2),
+                tone(Waveform::Sine, 392.0, 196.0, 0.2),
+            ],
+            Sfx::DevilEffect => vec![tone(Waveform::Square, 220.0, 440.0, 0.25)],
+            Sfx::Explode => vec![tone(Waveform::Noise, 0.0, 0.0, 0.5)],
+            Sfx::GiantCat => vec![tone(Waveform::Sine, 200.0, 70.0, 0.8)],
+            Sfx::Click => vec![tone(Waveform::Square, 1200.0, 1200.0, 0.03)],
+        }
+    }
+}
+
+/// One loaded variant of an effect, with a sound per allowed voice so copies can overlap.
+struct Variant<'a> {
+    voices: Vec<Sound<'a>>,
+}
+
+/// Plays sound effects on their own volume bus. Each effect has a list of variants picked
+/// at random, a little pitch variation, and a cap on how many copies play at once.
+pub struct SfxPlayer<'a> {
+    banks: HashMap<Sfx, Vec<Variant<'a>>>,
+    volume: f32,
+    rng: Rng,
+}
+
+impl<'a> SfxPlayer<'a> {
+    /// Loads `SFX_DIR/<name>*.wav|ogg|mp3` for each effect, synthesizing tones for any effect
+    /// without files.
+    pub fn load(audio: &'a RaylibAudio, assets: &Assets) -> Self {
+        let files = assets.list(config::SFX_DIR).unwrap_or_default();
+
+        let mut banks = HashMap::new();
+        for sfx in Sfx::ALL {
+            let mut waves = Vec::new();
+            for file in files.iter().filter(|file| matches_sfx(file, sfx.name())) {
+                let path = format!("{}/{}", config::SFX_DIR, file);
+                match assets.load_wave(audio, &path) {
+                    Ok(wave) => waves.push(wave),
+                    Err(err) => eprintln!("warning: sound effect {}: {}", path, err),
+                }
+            }
+            if waves.is_empty() {
+                for tone in sfx.tones() {
+                    match audio.new_wave_from_memory(".wav", &synthesize(tone)) {
+                        Ok(wave) => waves.push(wave),
+                        Err(err) => eprintln!("warning: sound effect {}: {}", sfx.name(), err),
+                    }
+                }
+            }
+            let variants = waves
+                .iter()
+                .map(|wave| Variant {
+                    voices: (0..sfx.max_voices())
+                        .filter_map(|_| audio.new_sound_from_wave(wave).ok())
+                        .collect(),
+                })
+                .collect();
+            banks.insert(sfx, variants);
+        }
+
+        Self {
+            banks,
+            volume: 1.0,
+            rng: Rng::new(rng::seed_from_clock()),
+        }
+    }
+
+    pub fn set_volume(&mut self, volume: f32) {
+        self.volume = volume;
+    }
+
+    /// Skipped when every voice of the effect is already playing.
+    pub fn play(&mut self, sfx: Sfx) {
+        if self.volume <= 0.0 {
+            return;
+        }
+        let Some(variants) = self.banks.get(&sfx) else {
+            return;
+        };
+        let busy: usize = variants
+            .iter()
+            .map(|v| v.voices.iter().filter(|s| s.is_playing()).count())
+            .sum();
+        if variants.is_empty() || busy >= sfx.max_voices() {
+            return;
+        }
+        let index = self.rng.range_i32(0..=variants.len() as i32 - 1) as usize;
+        let Some(voice) = variants[index].voices.iter().find(|s| !s.is_playing()) else {
+            return;
+        };
+        let spread = config::SFX_PITCH_VARIATION;
+        let pitch = 1.0 - spread + self.rng.unit() * spread * 2.0;
+        voice.set_volume(self.volume);
+        voice.set_pitch(pitch);
+        voice.play();
+    }
+}
+
+fn matches_sfx(file: &str, name: &str) -> bool {
+    let Some((stem, ext)) = file.rsplit_once('.') else {
+        return false;
+    };
+    if !matches!(ext, "wav" | "ogg" | "mp3") {
+        return false;
+    }
+    // `catch_2` is a variant of `catch`, but `catch_angel` is a different effect.
+    stem == name
+        || stem
+            .strip_prefix(name)
+            .and_then(|rest| rest.strip_prefix('_'))
+            .is_some_and(|rest| rest.chars().all(|c| c.is_ascii_digit()))
+}
+
+/// Renders `tone` as a 16-bit mono WAV file in memory.
+fn synthesize(tone: Tone) -> Vec<u8> {
+    let rate = config::SFX_SAMPLE_RATE;
+    let count = (tone.seconds * rate as f32) as u32;
+    let mut noise = Rng::new(0x5EED);
+    let mut phase = 0.0f32;
+    let mut samples = Vec::with_capacity(count as usize * 2);
+    for i in 0..count {
+        let t = i as f32 / count as f32;
+        let freq = tone.from + (tone.to - tone.from) * t;
+        phase = (phase + freq / rate as f32).fract();
+        let value = match tone.wave {
+            Waveform::Sine => (phase * std::f32::consts::TAU).sin(),
+            Waveform::Square => if phase < 0.5 { 0.6 } else { -0.6 },
+            Waveform::Noise => noise.unit() * 2.0 - 1.0,
+        };
+        // Quick attack, linear fade out, so nothing clicks.
+        let envelope = (t * 40.0).min(1.0) * (1.0 - t);
+        let sample = (value * envelope * 0.5 * i16::MAX as f32) as i16;
+        samples.extend_from_slice(&sample.to_le_bytes());
+    }
+
+    let mut wav = Vec::with_capacity(44 + samples.len());
+    wav.extend_from_slice(b"RIFF");
+    wav.extend_from_slice(&(36 + samples.len() as u32).to_le_bytes());
+    wav.extend_from_slice(b"WAVEfmt ");
+    wav.extend_from_slice(&16u32.to_le_bytes());
+    wav.extend_from_slice(&1u16.to_le_bytes()); // PCM
+    wav.extend_from_slice(&1u16.to_le_bytes()); // mono
+    wav.extend_from_slice(&rate.to_le_bytes());
+    wav.extend_from_slice(&(rate * 2).to_le_bytes());
+    wav.extend_from_slice(&2u16.to_le_bytes());
+    wav.extend_from_slice(&16u16.to_le_bytes());
+    wav.extend_from_slice(b"data");
+    wav.extend_from_slice(&(samples.len() as u32).to_le_bytes());
+    wav.extend_from_slice(&samples);
+    wav
+}
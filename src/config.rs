@@ -3,6 +3,10 @@ use raylib::prelude::Color;
 // window
 pub const SCREEN_W: i32 = 640;
 pub const SCREEN_H: i32 = 480;
+pub const WINDOW_MIN_W: i32 = 320;
+pub const WINDOW_MIN_H: i32 = 240;
+pub const WINDOW_SCALES: [f32; 3] = [1.0, 1.5, 2.0];
+pub const VOLUME_STEP: f32 = 0.1;
 pub const TARGET_FPS: u32 = 120;
 
 // colors
@@ -21,8 +25,19 @@ pub const COLOR_MENU_CIRCLE: Color = Color::new(255, 228, 241, 120);
 // scoring
 pub const GAME_SCORE_LABEL: &str = "GAME_SCORE";
 pub const SCORES_FILE: &str = "scores.txt";
+pub const DAILY_SCORES_FILE: &str = "scores_daily.txt";
+pub const PROFILE_FILE: &str = "profile.txt";
+pub const CONFIG_DIR_NAME: &str = "bucket-cat";
+pub const SETTINGS_FILE: &str = "settings.txt";
+pub const TIME_ATTACK_SHORT: u32 = 60;
+pub const TIME_ATTACK_LONG: u32 = 120;
+pub const TARGET_POINTS: i32 = 50;
 pub const NORMAL_CAT_SCORE: i32 = 1;
 pub const DEVIL_CAT_SCORE: i32 = 0;
+pub const GOLDEN_CAT_SCORE: i32 = 10;
+pub const GHOST_CAT_SCORE: i32 = 2;
+pub const BOMB_CAT_SCORE: i32 = 0;
+pub const HEART_PICKUP_SCORE: i32 = 0;
 
 // bucket
 pub const BUCKET_W: f32 = 48.0;
@@ -32,10 +47,10 @@ pub const BUCKET_MAX_SPEED: f32 = 420.0;
 pub const BUCKET_FRICTION: f32 = 8.0;
 pub const BUCKET_Y_OFFSET: f32 = 60.0;
 pub const BUCKET_DRAW_SCALE: f32 = 2.0;
-pub const BUCKET_FRAME_COUNT: usize = 2;
-pub const BUCKET_FRAME_COLS: i32 = 2;
-pub const BUCKET_FRAME_ROWS: i32 = 1;
-pub const BUCKET_ANIM_FPS: f32 = 6.0;
+pub const BUCKET_IDLE_FPS: f32 = 6.0;
+pub const BUCKET_CATCH_FPS: f32 = 12.0;
+pub const BUCKET_PANIC_FPS: f32 = 12.0;
+pub const BUCKET_EXPLODE_FPS: f32 = 10.0;
 
 // falling object
 pub const OBJ_RADIUS: f32 = 8.0;
@@ -43,11 +58,50 @@ pub const OBJ_GRAVITY: f32 = 400.0;
 pub const OBJ_BASE_SPEED: f32 = 20.0;
 pub const OBJ_SPEED_SCALE: f32 = 0.01;
 pub const OBJ_MAX_SPEED: f32 = 320.0;
-pub const OBJ_FRAME_COUNT: usize = 12;
-pub const OBJ_FRAME_COLS: i32 = 3;
-pub const OBJ_FRAME_ROWS: i32 = 4;
 pub const OBJ_ANIM_FPS: f32 = 10.0;
 pub const OBJ_DRAW_SCALE: f32 = 1.2;
+pub const CAT_SQUASH_FPS: f32 = 16.0;
+pub const CAT_SQUASH_STRETCH: f32 = 0.5;
+pub const CAT_SQUASH_FLATTEN: f32 = 0.7;
+
+// special cats
+pub const GOLDEN_SPEED_MULT: f32 = 3.0;
+pub const GOLDEN_MAX_SPEED: f32 = 480.0;
+pub const GHOST_FLICKER_PERIOD: f32 = 1.4;
+pub const GHOST_HIDDEN_FRACTION: f32 = 0.45;
+pub const SPLIT_HEIGHT_FRACTION: f32 = 0.5;
+pub const SPLIT_CHILD_SCALE: f32 = 0.65;
+pub const SPLIT_CHILD_OFFSET: f32 = 22.0;
+pub const SPLIT_CHILD_DRIFT: f32 = 60.0;
+pub const COLOR_GOLDEN_CAT: Color = Color::new(255, 214, 90, 255);
+pub const COLOR_GHOST_CAT: Color = Color::new(210, 225, 255, 150);
+pub const COLOR_SPLIT_CAT: Color = Color::new(196, 170, 255, 255);
+pub const COLOR_BOMB_CAT: Color = Color::new(90, 90, 96, 255);
+
+// cat motion
+pub const MOTION_RAMP_START: f32 = 10.0;
+pub const MOTION_RAMP_DURATION: f32 = 90.0;
+pub const MOTION_MAX_CHANCE: f32 = 0.6;
+pub const DRIFT_MIN_SPEED: f32 = 30.0;
+pub const DRIFT_MAX_SPEED: f32 = 90.0;
+pub const BOUNCE_MIN_SPEED: f32 = 80.0;
+pub const BOUNCE_MAX_SPEED: f32 = 160.0;
+pub const SINE_MIN_AMPLITUDE: f32 = 24.0;
+pub const SINE_MAX_AMPLITUDE: f32 = 64.0;
+pub const SINE_FREQUENCY: f32 = 0.8;
+pub const PARACHUTE_MAX_SPEED: f32 = 70.0;
+pub const PARACHUTE_SWAY: f32 = 18.0;
+pub const PARACHUTE_SWAY_FREQ: f32 = 0.5;
+pub const ANGEL_PARACHUTE_CHANCE: f32 = 0.5;
+pub const PREDICT_STEP: f32 = 1.0 / 60.0;
+pub const PREDICT_MAX_TIME: f32 = 6.0;
+pub const COLOR_LANDING_MARKER: Color = Color::new(255, 255, 255, 140);
+pub const COLOR_LANDING_MARKER_DANGER: Color = Color::new(255, 80, 80, 140);
+
+// Colour-blind palette (blue/orange, safe for red-green colour blindness)
+pub const COLOR_CB_SAFE: Color = Color::new(86, 180, 233, 160);
+pub const COLOR_CB_DANGER: Color = Color::new(230, 159, 0, 200);
+pub const COLOR_CB_PLAYER_TWO: Color = Color::new(240, 228, 66, 255);
 
 // spawn
 pub const SPAWN_INTERVAL: f32 = 0.8;
@@ -57,6 +111,12 @@ pub const DEVIL_RATE_LATE: f32 = 0.20;
 pub const DEVIL_RATE_EARLY_END: f32 = 30.0;
 pub const DEVIL_RATE_MID_END: f32 = 90.0;
 pub const ANGEL_RATE: f32 = 0.15;
+pub const GOLDEN_RATE: f32 = 0.02;
+pub const GHOST_RATE: f32 = 0.06;
+pub const SPLIT_RATE: f32 = 0.06;
+pub const BOMB_RATE: f32 = 0.05;
+pub const BOMB_START_TIME: f32 = 15.0;
+pub const HEART_RATE: f32 = 0.02;
 pub const EXPLODE_RATE_IN_DEVIL: f32 = 0.05;
 pub const DEVIL_COOLDOWN: f32 = 0.8;
 
@@ -75,22 +135,183 @@ pub const ANGRY_BAR_MIN_MAX: i32 = 15;
 pub const ANGRY_BAR_MAX_MAX: i32 = 25;
 pub const ANGRY_PENALTY_NORMAL: i32 = 1;
 pub const ANGRY_PENALTY_ANGEL: i32 = 3;
+pub const ANGRY_PENALTY_BOMB: i32 = 4;
 pub const DEVIL_MISS_STREAK_LIMIT: i32 = 5;
 pub const DEVIL_MISS_PENALTY: i32 = 1;
+pub const ANGRY_DECAY_STREAK: i32 = 5;
+pub const ANGRY_DECAY_INTERVAL: f32 = 3.0;
+
+// anger stages (fraction of the angry bar)
+pub const ANGER_ANNOYED_AT: f32 = 0.35;
+pub const ANGER_ANGRY_AT: f32 = 0.6;
+pub const ANGER_FURIOUS_AT: f32 = 0.85;
+pub const ANGER_WARNING_AT: f32 = 0.8;
+pub const ANGER_PULSE_SPEED: f32 = 8.0;
+pub const ANGER_SHAKE_MAX: f32 = 4.0;
+
+// camera
+pub const CAMERA_TRAUMA_DECAY: f32 = 1.4;
+pub const CAMERA_SHAKE_MAX_OFFSET: f32 = 14.0;
+pub const CAMERA_SHAKE_MAX_ANGLE: f32 = 2.5;
+pub const CAMERA_TRAUMA_EXPLOSION: f32 = 0.6;
+pub const CAMERA_TRAUMA_GIANT_CAT: f32 = 1.0;
+pub const CAMERA_TRAUMA_DEVIL: f32 = 0.3;
+pub const CAMERA_PUNCH_EXPLOSION: f32 = 0.06;
+pub const CAMERA_PUNCH_BIG_CATCH: f32 = 0.04;
+pub const CAMERA_PUNCH_DECAY: f32 = 8.0;
+pub const CAMERA_REDUCED_MOTION: f32 = 0.4;
+pub const HIT_STOP_BIG_CATCH: f32 = 0.08;
+pub const ANGER_DEVIL_SCALE_ANNOYED: f32 = 1.15;
+pub const ANGER_DEVIL_SCALE_ANGRY: f32 = 1.3;
+pub const ANGER_DEVIL_SCALE_FURIOUS: f32 = 1.5;
+pub const ANGER_MUSIC_PITCH_ANNOYED: f32 = 1.03;
+pub const ANGER_MUSIC_PITCH_ANGRY: f32 = 1.07;
+pub const ANGER_MUSIC_PITCH_FURIOUS: f32 = 1.12;
+pub const COLOR_TINT_ANNOYED: Color = Color::new(255, 236, 226, 255);
+pub const COLOR_TINT_ANGRY: Color = Color::new(255, 208, 192, 255);
+pub const COLOR_TINT_FURIOUS: Color = Color::new(255, 168, 158, 255);
+pub const COLOR_BAR_CALM: Color = Color::new(255, 140, 170, 255);
+pub const COLOR_BAR_ANNOYED: Color = Color::new(255, 170, 60, 255);
+pub const COLOR_BAR_ANGRY: Color = Color::new(230, 41, 55, 255);
+pub const COLOR_BAR_FURIOUS: Color = Color::new(160, 0, 20, 255);
+
+// classic lives
+pub const CLASSIC_LIVES: i32 = 5;
+pub const HEART_HUD_SIZE: f32 = 22.0;
+pub const HEART_HUD_GAP: f32 = 6.0;
+pub const HEART_PICKUP_SIZE: f32 = 18.0;
+pub const COLOR_HEART: Color = Color::new(235, 70, 120, 255);
+pub const COLOR_HEART_EMPTY: Color = Color::new(120, 60, 90, 140);
+
+// daily challenge
+pub const DAILY_MODIFIER_COUNT: usize = 2;
+pub const DAILY_FAST_CATS_SCALE: f32 = 1.35;
+pub const DAILY_TINY_BUCKET_SCALE: f32 = 0.6;
+pub const DAILY_INVERTED_START_DURATION: f32 = 12.0;
+
+// campaign
+pub const LEVELS_DIR: &str = "levels";
+pub const LEVEL_THREE_STAR_ANGER: f32 = 0.25;
+pub const LEVEL_TWO_STAR_ANGER: f32 = 0.6;
+pub const COLOR_STAR: Color = Color::new(255, 196, 40, 255);
+pub const COLOR_STAR_EMPTY: Color = Color::new(200, 170, 185, 160);
+
+// devil queen boss
+pub const BOSS_INTERVAL: f32 = 75.0;
+pub const BOSS_HEALTH: i32 = 6;
+pub const BOSS_HEALTH_STEP: i32 = 2;
+pub const BOSS_HOLY_DAMAGE: i32 = 1;
+pub const BOSS_SIZE: f32 = 96.0;
+pub const BOSS_Y: f32 = 110.0;
+pub const BOSS_SPEED: f32 = 90.0;
+pub const BOSS_ENTER_TIME: f32 = 2.0;
+pub const BOSS_EXIT_TIME: f32 = 1.5;
+pub const BOSS_FALL_SPEED: f32 = 160.0;
+pub const BOSS_MAX_DURATION: f32 = 40.0;
+pub const BOSS_THROW_INTERVAL: f32 = 2.2;
+pub const BOSS_THROW_SPEED: f32 = 60.0;
+pub const BOSS_HOLY_INTERVAL: f32 = 2.6;
+pub const BOSS_FAN_DRIFT: f32 = 70.0;
+pub const BOSS_RAIN_COUNT: usize = 4;
+pub const BOSS_BOMB_SPACING: f32 = 40.0;
+pub const BOSS_HIT_FLASH: f32 = 0.25;
+pub const BOSS_ANIM_FPS: f32 = 8.0;
+pub const BOSS_SCORE: i32 = 50;
+pub const BOSS_CALM_POINTS: i32 = 8;
+pub const BOSS_BAR_WIDTH: f32 = 240.0;
+pub const BOSS_BAR_HEIGHT: f32 = 10.0;
+pub const COLOR_BOSS_HIT: Color = Color::new(255, 120, 120, 255);
+pub const COLOR_BOSS_CROWN: Color = Color::new(255, 200, 60, 255);
+pub const COLOR_BOSS_BAR: Color = Color::new(170, 40, 120, 255);
+pub const COLOR_BOSS_BAR_BG: Color = Color::new(60, 20, 45, 180);
+
+// co-op
+pub const MAX_PLAYERS: usize = 2;
+pub const COLOR_PLAYER_TWO: Color = Color::new(170, 215, 255, 255);
+
+// Split-screen versus
+pub const VERSUS_DIVIDER_WIDTH: f32 = 4.0;
+
+// LAN play
+pub const NET_PORT: u16 = 47810;
+pub const NET_DEFAULT_ADDRESS: &str = "127.0.0.1";
+pub const NET_TICK: f32 = 1.0 / 60.0;
+pub const NET_INPUT_DELAY: u32 = 3;
+pub const NET_MAX_CATCHUP: u32 = 8;
+pub const NET_MAX_PACKET_INPUTS: usize = 64;
+pub const NET_HASH_INTERVAL: u32 = 60;
+pub const NET_HELLO_INTERVAL: f32 = 0.5;
+pub const NET_TIMEOUT: f32 = 5.0;
+pub const NET_STALL_NOTICE: f32 = 0.5;
+
+// Music mixing
+pub const MUSIC_DIR: &str = "sound_effects";
+pub const PLAYLIST_FILE: &str = "sound_effects/playlist.ron";
+pub const MUSIC_TITLE_TIME: f32 = 3.0;
+pub const MUSIC_CROSSFADE: f32 = 1.2;
+pub const MUSIC_DUCK_LEVEL: f32 = 0.35;
+pub const MUSIC_DUCK_FADE: f32 = 0.15;
+pub const MUSIC_LAYER_FADE: f32 = 2.0;
+pub const MUSIC_MUFFLE_FADE: f32 = 0.3;
+pub const MUSIC_MUFFLE_CUTOFF: f32 = 600.0;
+pub const AUDIO_MIX_RATE: f32 = 48000.0;
+// Intensity: half from how long the run has gone on, half from the angry bar.
+pub const MUSIC_INTENSITY_RAMP_TIME: f32 = 120.0;
+pub const MUSIC_INTENSITY_TIME_WEIGHT: f32 = 0.5;
+pub const MUSIC_INTENSITY_DEVIL_BOOST: f32 = 0.25;
+pub const MUSIC_TEMPO_RAMP: f32 = 0.05;
+
+// Sound effects
+pub const SFX_DIR: &str = "sfx";
+pub const SFX_SAMPLE_RATE: u32 = 22050;
+pub const SFX_PITCH_VARIATION: f32 = 0.08;
+pub const SFX_DUCK_TIME: f32 = 0.6;
 
 pub const GIANT_CAT_SPEED: f32 = 210.0;
-pub const CRYING_CAT_FRAME_W: f32 = 64.0;
-pub const CRYING_CAT_FRAME_H: f32 = 52.0;
-pub const CRYING_CAT_FRAMES: usize = 4;
-pub const CRYING_CAT_FPS: f32 = 8.0; 
+pub const CRYING_CAT_FPS: f32 = 8.0;
 pub const CRYING_CAT_SCALE: f32 = 10.0;
 pub const GIANT_CAT_LANDED_DELAY: f32 = 2.0;
+// Where the crying cat's eyes are, as fractions of its size from the top centre.
+pub const GIANT_CAT_EYE_X: f32 = 0.2;
+pub const GIANT_CAT_EYE_Y: f32 = 0.45;
 
-pub const DEVIL_EXPLODE_ANIM_DURATION: f32 = 0.9;
 pub const DEVIL_EFFECT_MESSAGE_DURATION: f32 = 2.4;
 
 // input tuning
+pub const AUTOPILOT_GAIN: f32 = 40.0;
 pub const GAMEPAD_DEADZONE: f32 = 0.2;
 pub const KEYBOARD_AXIS: f32 = 1.0;
 pub const MOVE_AXIS_MIN: f32 = -1.0;
 pub const MOVE_AXIS_MAX: f32 = 1.0;
+
+// Assets; the directories and files above are relative to the assets root
+pub const ASSETS_FLAG: &str = "--assets";
+pub const ASSETS_ENV: &str = "BUCKET_CAT_ASSETS";
+pub const ASSETS_DIR_NAME: &str = "assets";
+pub const ASSETS_DEV_DIR: &str = "src/assets";
+pub const PLACEHOLDER_SIZE: i32 = 64;
+pub const PLACEHOLDER_CHECKS: i32 = 8;
+pub const COLOR_PLACEHOLDER_A: Color = Color::new(255, 0, 255, 255);
+pub const COLOR_PLACEHOLDER_B: Color = Color::new(30, 30, 30, 255);
+
+// particles
+pub const PARTICLE_POOL_SIZE: usize = 1024;
+pub const COLOR_SPARK_NORMAL: Color = Color::new(255, 250, 235, 255);
+pub const COLOR_SPARK_ANGEL: Color = Color::new(190, 230, 255, 255);
+pub const COLOR_SPARK_DEVIL: Color = Color::new(255, 80, 70, 255);
+pub const COLOR_SPARK_BOMB: Color = Color::new(255, 140, 40, 255);
+pub const COLOR_TEAR: Color = Color::new(120, 190, 255, 230);
+pub const ATLAS_MAX_WIDTH: i32 = 1024;
+pub const ATLAS_PADDING: i32 = 2;
+
+// debug overlay and console
+pub const DEBUG_FRAME_HISTORY: usize = 120;
+pub const DEBUG_GRAPH_HEIGHT: f32 = 40.0;
+pub const DEBUG_PANEL_WIDTH: f32 = 300.0;
+pub const COLOR_DEBUG_HITBOX: Color = Color::new(80, 255, 120, 220);
+pub const COLOR_DEBUG_SLOW: Color = Color::new(255, 90, 90, 220);
+pub const COLOR_DEBUG_PANEL: Color = Color::new(0, 0, 0, 170);
+pub const CONSOLE_MAX_INPUT: usize = 60;
+pub const CONSOLE_LOG_LINES: usize = 6;
+pub const DEBUG_TIME_SCALE_MIN: f32 = 0.05;
+pub const DEBUG_TIME_SCALE_MAX: f32 = 4.0;
@@ -0,0 +1,141 @@
+use raylib::prelude::*;
+use crate::config;
+use crate::game::effects::DevilEffect;
+use crate::game::objects::CatKind;
+
+/// A parsed console line, for the app to carry out.
+pub enum Command {
+    /// `x` defaults to the middle of the arena.
+    Spawn { kind: CatKind, x: Option<f32> },
+    Effect(DevilEffect),
+    Angry(i32),
+    Seed(u64),
+    TimeScale(f32),
+}
+
+const HELP: &str = "spawn <kind> [x] | effect <name> | angry <points> | seed <n> | timescale <x>";
+
+/// The backtick developer console: one input line over a short log.
+pub struct Console {
+    open: bool,
+    line: String,
+    log: Vec<String>,
+}
+
+impl Console {
+    pub fn new() -> Self {
+        Self {
+            open: false,
+            line: String::new(),
+            log: vec![HELP.to_string()],
+        }
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.open
+    }
+
+    /// Backtick opens and closes it; while open it takes the keyboard. Returns a command
+    /// once Enter is pressed on a line that parses.
+    pub fn update_input(&mut self, rl: &mut RaylibHandle) -> Option<Command> {
+        if rl.is_key_pressed(KeyboardKey::KEY_GRAVE) {
+            self.open = !self.open;
+        }
+        if !self.open {
+            return None;
+        }
+        while let Some(c) = rl.get_char_pressed() {
+            if c != '`' && !c.is_control() && self.line.len() < config::CONSOLE_MAX_INPUT {
+                self.line.push(c);
+            }
+        }
+        if rl.is_key_pressed(KeyboardKey::KEY_BACKSPACE) {
+            self.line.pop();
+        }
+        if !rl.is_key_pressed(KeyboardKey::KEY_ENTER) {
+            return None;
+        }
+        let line = std::mem::take(&mut self.line);
+        if line.trim().is_empty() {
+            return None;
+        }
+        self.log(format!("> {}", line));
+        match parse(&line) {
+            Ok(command) => Some(command),
+            Err(err) => {
+                self.log(err);
+                None
+            }
+        }
+    }
+
+    pub fn log(&mut self, text: impl Into<String>) {
+        self.log.push(text.into());
+        let excess = self.log.len().saturating_sub(config::CONSOLE_LOG_LINES);
+        self.log.drain(..excess);
+    }
+
+    pub fn draw(&self, d: &mut RaylibDrawHandle, screen_w: f32) {
+        if !self.open {
+            return;
+        }
+        let font_size = 12;
+        let line_h = 14;
+        let height = (self.log.len() as i32 + 1) * line_h + 8;
+        d.draw_rectangle(0, 0, screen_w as i32, height, config::COLOR_DEBUG_PANEL);
+        for (i, text) in self.log.iter().enumerate() {
+            d.draw_text(text, 6, 4 + i as i32 * line_h, font_size, Color::LIGHTGRAY);
+        }
+        let prompt = format!("> {}_", self.line);
+        d.draw_text(&prompt, 6, 4 + self.log.len() as i32 * line_h, font_size, Color::WHITE);
+    }
+}
+
+fn parse(line: &str) -> Result<Command, String> {
+    let mut words = line.split_whitespace();
+    let name = words.next().unwrap_or_default();
+    let args: Vec<&str> = words.collect();
+    let arg = |i: usize| args.get(i).copied().ok_or_else(|| HELP.to_string());
+    match name {
+        "spawn" => {
+            let kind = arg(0)?;
+            let kind = CatKind::ALL
+                .into_iter()
+                .find(|k| k.name() == kind)
+                .ok_or_else(|| format!("unknown cat {}", kind))?;
+            let x = match args.get(1) {
+                Some(x) => Some(
+                    x.parse::<f32>()
+                        .ok()
+                        .filter(|x| x.is_finite())
+                        .ok_or_else(|| format!("bad x {}", x))?,
+                ),
+                None => None,
+            };
+            Ok(Command::Spawn { kind, x })
+        }
+        "effect" => {
+            let effect = arg(0)?;
+            DevilEffect::ALL
+                .into_iter()
+                .find(|e| e.name() == effect)
+                .map(Command::Effect)
+                .ok_or_else(|| format!("unknown effect {}", effect))
+        }
+        "angry" => arg(0)?
+            .parse()
+            .map(Command::Angry)
+            .map_err(|_| "angry takes whole points".to_string()),
+        "seed" => arg(0)?
+            .parse()
+            .map(Command::Seed)
+            .map_err(|_| "seed takes a number".to_string()),
+        "timescale" => arg(0)?
+            .parse::<f32>()
+            .ok()
+            .filter(|scale| scale.is_finite())
+            .map(Command::TimeScale)
+            .ok_or_else(|| "timescale takes a number".to_string()),
+        _ => Err(HELP.to_string()),
+    }
+}
@@ -0,0 +1,126 @@
+pub mod console;
+
+use std::collections::VecDeque;
+use raylib::prelude::*;
+use crate::config;
+use crate::game::world::World;
+
+/// F3 tuning view: hitboxes in the world, numbers and a frame-time graph on top.
+pub struct DebugOverlay {
+    visible: bool,
+    frame_times: VecDeque<f32>,
+}
+
+impl DebugOverlay {
+    pub fn new() -> Self {
+        Self {
+            visible: false,
+            frame_times: VecDeque::with_capacity(config::DEBUG_FRAME_HISTORY),
+        }
+    }
+
+    pub fn toggle(&mut self) {
+        self.visible = !self.visible;
+    }
+
+    pub fn visible(&self) -> bool {
+        self.visible
+    }
+
+    /// Called every frame, shown or not, so the graph is full when it opens.
+    pub fn record(&mut self, dt: f32) {
+        if self.frame_times.len() == config::DEBUG_FRAME_HISTORY {
+            self.frame_times.pop_front();
+        }
+        self.frame_times.push_back(dt);
+    }
+
+    /// The shapes the catch test sweeps between frames, where they are now, drawn through
+    /// the world camera.
+    pub fn draw_hitboxes(&self, d: &mut RaylibDrawHandle, world: &World) {
+        for obj in &world.objects {
+            d.draw_circle_lines(obj.pos.x as i32, obj.pos.y as i32, obj.radius, config::COLOR_DEBUG_HITBOX);
+        }
+        for player in &world.players {
+            d.draw_rectangle_lines_ex(player.bucket.rect(), 1.0, config::COLOR_DEBUG_HITBOX);
+        }
+    }
+
+    pub fn draw_panel(&self, d: &mut RaylibDrawHandle, world: &World, time_scale: f32) {
+        let dt = self.frame_times.back().copied().unwrap_or(0.0);
+        let mut lines = vec![
+            format!("FPS {}  frame {:.2} ms", d.get_fps(), dt * 1000.0),
+            format!(
+                "time {:.1}s  difficulty x{:.2}  timescale x{}",
+                world.elapsed_time(),
+                world.difficulty(),
+                time_scale
+            ),
+            format!(
+                "angry {}/{}  lives {}/{}",
+                world.angry_points, world.max_angry_points, world.lives, world.max_lives
+            ),
+            format!(
+                "objects {}  particles {}",
+                world.objects.len(),
+                world.particles().particles().len()
+            ),
+        ];
+        match world.spawn_rates() {
+            Some(rates) => {
+                lines.push(format!("spawn every {:.2}s", config::SPAWN_INTERVAL));
+                let odds: Vec<String> = rates
+                    .iter()
+                    .map(|(kind, rate)| format!("{} {:.2}", kind.name(), rate))
+                    .collect();
+                for chunk in odds.chunks(4) {
+                    lines.push(format!("  {}", chunk.join("  ")));
+                }
+            }
+            None => lines.push("spawn: level script".to_string()),
+        }
+        for (name, time) in world.effect_timers() {
+            lines.push(format!("effect {} {:.1}s", name, time));
+        }
+
+        let font_size = 10;
+        let line_h = 12;
+        let graph_h = config::DEBUG_GRAPH_HEIGHT;
+        let panel = Rectangle::new(
+            8.0,
+            90.0,
+            config::DEBUG_PANEL_WIDTH,
+            (lines.len() as i32 * line_h) as f32 + graph_h + 16.0,
+        );
+        d.draw_rectangle_rec(panel, config::COLOR_DEBUG_PANEL);
+        for (i, line) in lines.iter().enumerate() {
+            d.draw_text(
+                line,
+                panel.x as i32 + 6,
+                panel.y as i32 + 4 + i as i32 * line_h,
+                font_size,
+                Color::WHITE,
+            );
+        }
+
+        // One bar per frame, scaled so the 60 FPS budget sits halfway up.
+        let base_y = panel.y + panel.height - 6.0;
+        let budget = 1.0 / 60.0;
+        let bar_w = (panel.width - 12.0) / config::DEBUG_FRAME_HISTORY as f32;
+        for (i, frame) in self.frame_times.iter().enumerate() {
+            let h = (frame / budget * graph_h / 2.0).min(graph_h);
+            let color = if *frame > budget { config::COLOR_DEBUG_SLOW } else { config::COLOR_DEBUG_HITBOX };
+            d.draw_rectangle_rec(
+                Rectangle::new(panel.x + 6.0 + i as f32 * bar_w, base_y - h, bar_w.max(1.0), h),
+                color,
+            );
+        }
+        d.draw_line(
+            panel.x as i32 + 6,
+            (base_y - graph_h / 2.0) as i32,
+            (panel.x + panel.width) as i32 - 6,
+            (base_y - graph_h / 2.0) as i32,
+            Color::WHITE.alpha(0.5),
+        );
+    }
+}
@@ -0,0 +1,71 @@
+use raylib::prelude::Color;
+use crate::config;
+
+/// How upset the cat is, derived from the angry bar (or lost hearts) as a fraction.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum AngerStage {
+    Calm,
+    Annoyed,
+    Angry,
+    Furious,
+}
+
+impl AngerStage {
+    pub fn from_fraction(fraction: f32) -> Self {
+        if fraction >= config::ANGER_FURIOUS_AT {
+            AngerStage::Furious
+        } else if fraction >= config::ANGER_ANGRY_AT {
+            AngerStage::Angry
+        } else if fraction >= config::ANGER_ANNOYED_AT {
+            AngerStage::Annoyed
+        } else {
+            AngerStage::Calm
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            AngerStage::Calm => "Calm",
+            AngerStage::Annoyed => "Annoyed",
+            AngerStage::Angry => "Angry",
+            AngerStage::Furious => "Furious",
+        }
+    }
+
+    pub fn background_tint(self) -> Color {
+        match self {
+            AngerStage::Calm => Color::WHITE,
+            AngerStage::Annoyed => config::COLOR_TINT_ANNOYED,
+            AngerStage::Angry => config::COLOR_TINT_ANGRY,
+            AngerStage::Furious => config::COLOR_TINT_FURIOUS,
+        }
+    }
+
+    pub fn bar_color(self) -> Color {
+        match self {
+            AngerStage::Calm => config::COLOR_BAR_CALM,
+            AngerStage::Annoyed => config::COLOR_BAR_ANNOYED,
+            AngerStage::Angry => config::COLOR_BAR_ANGRY,
+            AngerStage::Furious => config::COLOR_BAR_FURIOUS,
+        }
+    }
+
+    pub fn music_pitch(self) -> f32 {
+        match self {
+            AngerStage::Calm => 1.0,
+            AngerStage::Annoyed => config::ANGER_MUSIC_PITCH_ANNOYED,
+            AngerStage::Angry => config::ANGER_MUSIC_PITCH_ANGRY,
+            AngerStage::Furious => config::ANGER_MUSIC_PITCH_FURIOUS,
+        }
+    }
+
+    /// Devils (and bombs) show up more often the angrier the cat gets.
+    pub fn devil_rate_scale(self) -> f32 {
+        match self {
+            AngerStage::Calm => 1.0,
+            AngerStage::Annoyed => config::ANGER_DEVIL_SCALE_ANNOYED,
+            AngerStage::Angry => config::ANGER_DEVIL_SCALE_ANGRY,
+            AngerStage::Furious => config::ANGER_DEVIL_SCALE_FURIOUS,
+        }
+    }
+}
@@ -0,0 +1,204 @@
+use std::collections::HashMap;
+use crate::render::atlas::SpriteId;
+
+/// What a clip does once it has shown its last frame.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum PlayMode {
+    Loop,
+    /// Holds the last frame and reports `finished`.
+    Once,
+    /// Plays forwards then backwards, forever.
+    PingPong,
+}
+
+/// Something a clip tells its owner when playback reaches a given frame.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum AnimEvent {
+    /// A caught cat has squashed flat and can be removed.
+    Vanish,
+}
+
+/// A named run of frames. The name picks the animation in the sprite sheet; the frame
+/// numbers here index into that animation's frame list. Declare clips as `static`s: the
+/// animator tells them apart by address.
+pub struct Clip {
+    pub name: &'static str,
+    pub frames: usize,
+    pub fps: f32,
+    pub mode: PlayMode,
+    pub events: &'static [(usize, AnimEvent)],
+}
+
+impl Clip {
+    /// Seconds `frame` is shown for: the sprite sheet's time for it if the sheet gives
+    /// one, otherwise one tick of `fps`.
+    fn frame_time(&self, times: Option<&[f32]>, frame: usize) -> f32 {
+        match times {
+            Some(times) => times[frame % times.len()],
+            None => 1.0 / self.fps.max(0.001),
+        }
+    }
+
+    /// Seconds for one pass through the frames.
+    fn length(&self, times: Option<&[f32]>) -> f32 {
+        (0..self.frames.max(1)).map(|frame| self.frame_time(times, frame)).sum()
+    }
+}
+
+/// Per-frame times from the sprite sheets, by sprite and animation name. A clip with no
+/// entry for its owner's sprite keeps its own `fps`.
+#[derive(Clone, Default)]
+pub struct FrameTimes(HashMap<SpriteId, HashMap<String, Vec<f32>>>);
+
+impl FrameTimes {
+    pub fn insert(&mut self, sprite: SpriteId, animation: &str, times: Vec<f32>) {
+        if !times.is_empty() {
+            self.0.entry(sprite).or_default().insert(animation.to_string(), times);
+        }
+    }
+
+    fn get(&self, sprite: SpriteId, animation: &str) -> Option<&[f32]> {
+        self.0.get(&sprite)?.get(animation).map(Vec::as_slice)
+    }
+}
+
+/// Plays one clip at a time and works out which frame is showing. The game owns these;
+/// the renderer only reads the clip name and frame. `sprite` is the sheet the owner is
+/// drawn from, which picks the frame times for the clip.
+#[derive(Clone)]
+pub struct Animator {
+    sprite: SpriteId,
+    clip: &'static Clip,
+    time: f32,
+}
+
+impl Animator {
+    pub fn new(sprite: SpriteId, clip: &'static Clip) -> Self {
+        Self { sprite, clip, time: 0.0 }
+    }
+
+    /// Starts `clip` from its first frame, even if it is already playing.
+    pub fn play(&mut self, clip: &'static Clip) {
+        self.clip = clip;
+        self.time = 0.0;
+    }
+
+    /// Switches to `clip` unless it is already playing, so a looping clip is not restarted.
+    pub fn switch(&mut self, clip: &'static Clip) {
+        if !self.is_playing(clip) {
+            self.play(clip);
+        }
+    }
+
+    pub fn is_playing(&self, clip: &Clip) -> bool {
+        std::ptr::eq(self.clip, clip)
+    }
+
+    /// Advances by `dt` without looking for events.
+    pub fn advance(&mut self, dt: f32) {
+        self.time += dt;
+    }
+
+    /// Advances by `dt` and returns the events of every frame entered on the way.
+    pub fn update(&mut self, dt: f32, times: &FrameTimes) -> Vec<AnimEvent> {
+        let before = self.step(times);
+        self.time += dt;
+        let after = self.step(times);
+        let mut events = Vec::new();
+        if self.clip.events.is_empty() {
+            return events;
+        }
+        for step in before + 1..=after {
+            if self.clip.mode == PlayMode::Once && step >= self.clip.frames {
+                break;
+            }
+            let frame = self.frame_at(step);
+            events.extend(
+                self.clip
+                    .events
+                    .iter()
+                    .filter(|(at, _)| *at == frame)
+                    .map(|(_, event)| *event),
+            );
+        }
+        events
+    }
+
+    pub fn clip(&self) -> &'static str {
+        self.clip.name
+    }
+
+    pub fn frame(&self, times: &FrameTimes) -> usize {
+        self.frame_at(self.step(times))
+    }
+
+    /// A one-shot clip that has played through. Looping clips never finish.
+    pub fn finished(&self, times: &FrameTimes) -> bool {
+        self.clip.mode == PlayMode::Once && self.time >= self.clip.length(self.times(times))
+    }
+
+    /// How far through one pass of the clip playback is, from 0.0 to 1.0.
+    pub fn progress(&self, times: &FrameTimes) -> f32 {
+        let length = self.clip.length(self.times(times));
+        match self.clip.mode {
+            PlayMode::Once => (self.time / length).min(1.0),
+            PlayMode::Loop | PlayMode::PingPong => self.time.rem_euclid(length) / length,
+        }
+    }
+
+    /// The sheet's frame times for the playing clip, if it has any.
+    fn times<'a>(&self, times: &'a FrameTimes) -> Option<&'a [f32]> {
+        times.get(self.sprite, self.clip.name)
+    }
+
+    /// Frames advanced since the clip started.
+    fn step(&self, times: &FrameTimes) -> usize {
+        let clip = self.clip;
+        let times = self.times(times);
+        if times.is_none() {
+            return (self.time * clip.fps).max(0.0) as usize;
+        }
+        // Frames have their own times: skip whole cycles, then walk the last one.
+        let period = self.period();
+        let cycle: f32 = (0..period)
+            .map(|step| clip.frame_time(times, self.frame_at(step)))
+            .sum();
+        let mut rest = self.time.max(0.0);
+        let mut step = 0;
+        if clip.mode != PlayMode::Once && cycle > 0.0 {
+            step = (rest / cycle) as usize * period;
+            rest = rest.rem_euclid(cycle);
+        }
+        for offset in 0..period {
+            let time = clip.frame_time(times, self.frame_at(offset));
+            if rest < time {
+                return step + offset;
+            }
+            rest -= time;
+        }
+        step + period
+    }
+
+    /// Steps before the frame sequence repeats.
+    fn period(&self) -> usize {
+        let frames = self.clip.frames.max(1);
+        match self.clip.mode {
+            PlayMode::Loop | PlayMode::Once => frames,
+            PlayMode::PingPong => (frames * 2).saturating_sub(2).max(1),
+        }
+    }
+
+    fn frame_at(&self, step: usize) -> usize {
+        let frames = self.clip.frames.max(1);
+        match self.clip.mode {
+            PlayMode::Loop => step % frames,
+            PlayMode::Once => step.min(frames - 1),
+            PlayMode::PingPong => {
+                // 0, 1, .. last, .. 1, then round again.
+                let period = self.period();
+                let t = step % period;
+                if t < frames { t } else { period - t }
+            }
+        }
+    }
+}
@@ -0,0 +1,29 @@
+use crate::config;
+use crate::game::objects::CatKind;
+use crate::game::world::World;
+
+/// Computer-controlled bucket: chases the soonest landing cat worth catching and
+/// returns a movement axis in the same range as `Input::move_x`.
+pub fn steer(world: &World, screen_w: f32) -> f32 {
+    let target = world
+        .landing_predictions(screen_w)
+        .into_iter()
+        .filter(|(kind, _)| wanted(*kind))
+        .min_by(|(_, a), (_, b)| a.time.total_cmp(&b.time));
+
+    let Some((_, landing)) = target else {
+        return 0.0;
+    };
+
+    let rect = world.bucket().rect();
+    let center_x = rect.x + rect.width / 2.0;
+    let axis = ((landing.pos.x - center_x) / config::AUTOPILOT_GAIN)
+        .clamp(config::MOVE_AXIS_MIN, config::MOVE_AXIS_MAX);
+
+    // The world flips the axis while controls are inverted, so pre-flip it here.
+    if world.controls_inverted() { -axis } else { axis }
+}
+
+fn wanted(kind: CatKind) -> bool {
+    !matches!(kind, CatKind::Devil | CatKind::Explode | CatKind::Bomb)
+}
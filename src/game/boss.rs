@@ -0,0 +1,218 @@
+use raylib::prelude::*;
+use crate::config;
+use crate::game::animator::{Animator, Clip, PlayMode};
+use crate::game::objects::{CatKind, FallingObject, Motion};
+use crate::render::atlas::SpriteId;
+use crate::rng::Rng;
+
+/// The Devil Queen's fight, from flying in to either falling defeated or giving up and leaving.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum BossPhase {
+    Entering,
+    Attacking,
+    Defeated,
+    Leaving,
+}
+
+#[derive(Clone, Copy)]
+enum Pattern {
+    Fan,
+    Rain,
+    BombLine,
+}
+
+static FLY: Clip = Clip {
+    name: "boss",
+    frames: 12,
+    fps: config::BOSS_ANIM_FPS,
+    mode: PlayMode::Loop,
+    events: &[],
+};
+
+const PATTERNS: [Pattern; 3] = [Pattern::Fan, Pattern::Rain, Pattern::BombLine];
+
+pub struct Boss {
+    pub pos: Vector2,
+    phase: BossPhase,
+    phase_time: f32,
+    health: i32,
+    max_health: i32,
+    direction: f32,
+    throw_timer: f32,
+    holy_timer: f32,
+    hit_timer: f32,
+    anim: Animator,
+}
+
+impl Boss {
+    /// Each later appearance takes a few more holy cats to bring down.
+    pub fn new(screen_w: f32, appearances: i32) -> Self {
+        let max_health = config::BOSS_HEALTH + appearances * config::BOSS_HEALTH_STEP;
+        Self {
+            pos: Vector2::new(screen_w / 2.0, -config::BOSS_SIZE),
+            phase: BossPhase::Entering,
+            phase_time: 0.0,
+            health: max_health,
+            max_health,
+            direction: 1.0,
+            throw_timer: 0.0,
+            holy_timer: 0.0,
+            hit_timer: 0.0,
+            anim: Animator::new(SpriteId::DevilCat, &FLY),
+        }
+    }
+
+    /// Advances the fight and returns any cats thrown or sent down this frame.
+    pub fn update(&mut self, dt: f32, screen_w: f32, rng: &mut Rng) -> Vec<FallingObject> {
+        self.phase_time += dt;
+        self.hit_timer = (self.hit_timer - dt).max(0.0);
+        self.anim.advance(dt);
+
+        let mut thrown = Vec::new();
+        match self.phase {
+            BossPhase::Entering => {
+                let t = (self.phase_time / config::BOSS_ENTER_TIME).min(1.0);
+                self.pos.y = -config::BOSS_SIZE + (config::BOSS_Y + config::BOSS_SIZE) * t;
+                if t >= 1.0 {
+                    self.set_phase(BossPhase::Attacking);
+                }
+            }
+            BossPhase::Attacking => {
+                self.pos.x += self.direction * config::BOSS_SPEED * dt;
+                let margin = config::BOSS_SIZE / 2.0;
+                if self.pos.x < margin || self.pos.x > screen_w - margin {
+                    self.pos.x = self.pos.x.clamp(margin, (screen_w - margin).max(margin));
+                    self.direction = -self.direction;
+                }
+
+                self.throw_timer += dt;
+                if self.throw_timer >= config::BOSS_THROW_INTERVAL {
+                    self.throw_timer = 0.0;
+                    let pattern = PATTERNS[rng.range_i32(0..=PATTERNS.len() as i32 - 1) as usize];
+                    thrown.extend(self.throw(pattern, screen_w, rng));
+                }
+
+                self.holy_timer += dt;
+                if self.holy_timer >= config::BOSS_HOLY_INTERVAL {
+                    self.holy_timer = 0.0;
+                    let x = rng.range_i32(20..=(screen_w as i32 - 20)) as f32;
+                    thrown.push(
+                        FallingObject::new(x, CatKind::Angel, config::BOSS_THROW_SPEED)
+                            .with_motion(Motion::Parachute),
+                    );
+                }
+
+                if self.phase_time >= config::BOSS_MAX_DURATION {
+                    self.set_phase(BossPhase::Leaving);
+                }
+            }
+            BossPhase::Defeated => {
+                self.pos.y += config::BOSS_FALL_SPEED * dt;
+            }
+            BossPhase::Leaving => {
+                self.pos.y -= config::BOSS_FALL_SPEED * dt;
+            }
+        }
+        thrown
+    }
+
+    fn throw(&self, pattern: Pattern, screen_w: f32, rng: &mut Rng) -> Vec<FallingObject> {
+        let throw_cat = |x: f32, kind: CatKind, motion: Motion| {
+            FallingObject::new(x, kind, config::BOSS_THROW_SPEED)
+                .with_start_y(self.pos.y + config::BOSS_SIZE / 2.0)
+                .with_motion(motion)
+        };
+        match pattern {
+            Pattern::Fan => [-1.0, 0.0, 1.0]
+                .iter()
+                .map(|side| {
+                    throw_cat(
+                        self.pos.x,
+                        CatKind::Devil,
+                        Motion::Drift {
+                            speed: side * config::BOSS_FAN_DRIFT,
+                        },
+                    )
+                })
+                .collect(),
+            Pattern::Rain => (0..config::BOSS_RAIN_COUNT)
+                .map(|_| {
+                    let x = rng.range_i32(20..=(screen_w as i32 - 20)) as f32;
+                    let kind = if rng.unit() < 0.5 {
+                        CatKind::Devil
+                    } else {
+                        CatKind::Bomb
+                    };
+                    throw_cat(x, kind, Motion::Straight)
+                })
+                .collect(),
+            Pattern::BombLine => [-1.0, 1.0]
+                .iter()
+                .map(|side| {
+                    throw_cat(
+                        self.pos.x + side * config::BOSS_BOMB_SPACING,
+                        CatKind::Bomb,
+                        Motion::Straight,
+                    )
+                })
+                .collect(),
+        }
+    }
+
+    fn set_phase(&mut self, phase: BossPhase) {
+        self.phase = phase;
+        self.phase_time = 0.0;
+    }
+
+    /// A holy cat landed in the bucket. Returns `true` on the hit that defeats her.
+    pub fn hit(&mut self, damage: i32) -> bool {
+        if self.phase != BossPhase::Attacking {
+            return false;
+        }
+        self.health = (self.health - damage).max(0);
+        self.hit_timer = config::BOSS_HIT_FLASH;
+        if self.health == 0 {
+            self.set_phase(BossPhase::Defeated);
+            return true;
+        }
+        false
+    }
+
+    /// Still in the fight, so regular spawning stays paused and the boss music plays.
+    pub fn fighting(&self) -> bool {
+        matches!(self.phase, BossPhase::Entering | BossPhase::Attacking)
+    }
+
+    pub fn finished(&self) -> bool {
+        match self.phase {
+            BossPhase::Defeated | BossPhase::Leaving => self.phase_time >= config::BOSS_EXIT_TIME,
+            _ => false,
+        }
+    }
+
+    pub fn phase(&self) -> BossPhase {
+        self.phase
+    }
+
+    pub fn health_fraction(&self) -> f32 {
+        self.health as f32 / self.max_health.max(1) as f32
+    }
+
+    pub fn animator(&self) -> &Animator {
+        &self.anim
+    }
+
+    pub fn flashing(&self) -> bool {
+        self.hit_timer > 0.0
+    }
+
+    /// Fades out while falling or leaving, from 1.0 to 0.0.
+    pub fn fade(&self) -> f32 {
+        match self.phase {
+            BossPhase::Defeated | BossPhase::Leaving => {
+                1.0 - (self.phase_time / config::BOSS_EXIT_TIME).clamp(0.0, 1.0)
+            }
+            _ => 1.0,
+        }
+    }
+}
@@ -1,26 +1,60 @@
 use raylib::prelude::*;
 use crate::config;
+use crate::game::animator::{Animator, Clip, FrameTimes, PlayMode};
+use crate::render::atlas::SpriteId;
+
+static IDLE: Clip = Clip {
+    name: "idle",
+    frames: 2,
+    fps: config::BUCKET_IDLE_FPS,
+    mode: PlayMode::Loop,
+    events: &[],
+};
+static CATCH: Clip = Clip {
+    name: "catch",
+    frames: 3,
+    fps: config::BUCKET_CATCH_FPS,
+    mode: PlayMode::Once,
+    events: &[],
+};
+static PANIC: Clip = Clip {
+    name: "panic",
+    frames: 2,
+    fps: config::BUCKET_PANIC_FPS,
+    mode: PlayMode::Loop,
+    events: &[],
+};
+static EXPLODE: Clip = Clip {
+    name: "explode",
+    frames: 4,
+    fps: config::BUCKET_EXPLODE_FPS,
+    mode: PlayMode::Once,
+    events: &[],
+};
 
 #[derive(Clone)]
 pub struct Bucket {
     pub pos: Vector2,
+    prev_pos: Vector2,
     vel: Vector2,
     pub size: Vector2,
-    anim_time: f32,
-    anim_frame: usize,
+    anim: Animator,
+    panicking: bool,
 }
 
 impl Bucket {
     pub fn new(screen_w: f32, screen_h: f32) -> Self {
+        let pos = Vector2::new(
+            screen_w / 2.0 - config::BUCKET_W / 2.0,
+            screen_h - config::BUCKET_Y_OFFSET,
+        );
         Self {
-            pos: Vector2::new(
-                screen_w / 2.0 - config::BUCKET_W / 2.0,
-                screen_h - config::BUCKET_Y_OFFSET,
-            ),
+            pos,
+            prev_pos: pos,
             vel: Vector2::new(0.0, 0.0),
             size: Vector2::new(config::BUCKET_W, config::BUCKET_H),
-            anim_time: 0.0,
-            anim_frame: 0,
+            anim: Animator::new(SpriteId::Bucket, &IDLE),
+            panicking: false,
         }
     }
 
@@ -33,7 +67,15 @@ impl Bucket {
         }
     }
 
-    pub fn update(&mut self, axis: f32, dt: f32, screen_w: f32, difficulty: f32) {
+    pub fn update(
+        &mut self,
+        axis: f32,
+        dt: f32,
+        screen_w: f32,
+        difficulty: f32,
+        times: &FrameTimes,
+    ) {
+        self.prev_pos = self.pos;
         let accel = config::BUCKET_ACCEL * difficulty;
         let max_speed = config::BUCKET_MAX_SPEED * difficulty;
         self.vel.x += axis * accel * dt;
@@ -56,18 +98,65 @@ impl Bucket {
             self.vel.x = 0.0;
         }
 
-        if config::BUCKET_FRAME_COUNT > 1 {
-            let frame_dt = 1.0 / config::BUCKET_ANIM_FPS.max(1.0);
-            self.anim_time += dt;
-            while self.anim_time >= frame_dt {
-                self.anim_time -= frame_dt;
-                self.anim_frame = (self.anim_frame + 1) % config::BUCKET_FRAME_COUNT;
-            }
+        self.anim.update(dt, times);
+        if self.anim.finished(times) {
+            self.anim.play(self.resting_clip());
+        }
+    }
+
+    /// Where the bucket was before this frame's `update`, at its current size.
+    pub fn prev_rect(&self) -> Rectangle {
+        Rectangle {
+            x: self.prev_pos.x,
+            y: self.prev_pos.y,
+            width: self.size.x,
+            height: self.size.y,
+        }
+    }
+
+    pub fn velocity_x(&self) -> f32 {
+        self.vel.x
+    }
+
+    pub fn set_velocity_x(&mut self, vel_x: f32) {
+        self.vel.x = vel_x;
+    }
+
+    /// Moves the bucket sideways without letting it leave the screen.
+    pub fn shift(&mut self, dx: f32, screen_w: f32) {
+        self.pos.x = (self.pos.x + dx).clamp(0.0, (screen_w - self.size.x).max(0.0));
+    }
+
+    pub fn place_at(&mut self, center_x: f32, screen_w: f32) {
+        self.pos.x = 0.0;
+        self.shift(center_x - self.size.x / 2.0, screen_w);
+    }
+
+    pub fn animator(&self) -> &Animator {
+        &self.anim
+    }
+
+    /// A quick wobble for a catch; an explosion already playing is not cut short.
+    pub fn react_to_catch(&mut self) {
+        if !self.anim.is_playing(&EXPLODE) {
+            self.anim.play(&CATCH);
+        }
+    }
+
+    pub fn explode(&mut self) {
+        self.anim.play(&EXPLODE);
+    }
+
+    /// Shakes in place while the cat is furious, once any reaction has played out.
+    pub fn set_panicking(&mut self, panicking: bool) {
+        self.panicking = panicking;
+        if self.anim.is_playing(&IDLE) || self.anim.is_playing(&PANIC) {
+            self.anim.switch(self.resting_clip());
         }
     }
 
-    pub fn frame(&self) -> usize {
-        self.anim_frame
+    fn resting_clip(&self) -> &'static Clip {
+        if self.panicking { &PANIC } else { &IDLE }
     }
 
     pub fn set_size(&mut self, size: Vector2, screen_w: f32, screen_h: f32) {
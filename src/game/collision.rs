@@ -1,12 +1,156 @@
 use::raylib::prelude::*;
 
 pub fn check_collision(center: Vector2, radius: f32, rect: Rectangle) -> bool {
+    distance_squared(center, rect) < radius * radius
+}
+
+/// Whether a circle moving from `from` to `to` touches a rectangle moving from `rect_from`
+/// to `rect_to` at any point on the way, so a fast cat or a long frame cannot carry it
+/// through the bucket between two checks. Both are taken to move in straight lines.
+pub fn check_swept_collision(
+    from: Vector2,
+    to: Vector2,
+    radius: f32,
+    rect_from: Rectangle,
+    rect_to: Rectangle,
+) -> bool {
+    if check_collision(to, radius, rect_to) {
+        return true;
+    }
+    // Seen from the rectangle only the circle moves, so sweep its path past `rect_to`.
+    let start = Vector2::new(from.x + rect_to.x - rect_from.x, from.y + rect_to.y - rect_from.y);
+    if segment_crosses(start, to, rect_to) {
+        return true;
+    }
+    // Otherwise the closest approach is at an end of the path or at a corner.
+    let corners = [
+        Vector2::new(rect_to.x, rect_to.y),
+        Vector2::new(rect_to.x + rect_to.width, rect_to.y),
+        Vector2::new(rect_to.x, rect_to.y + rect_to.height),
+        Vector2::new(rect_to.x + rect_to.width, rect_to.y + rect_to.height),
+    ];
+    let nearest = corners
+        .iter()
+        .map(|corner| segment_distance_squared(*corner, start, to))
+        .fold(distance_squared(start, rect_to), f32::min);
+    nearest < radius * radius
+}
+
+fn distance_squared(center: Vector2, rect: Rectangle) -> f32 {
     let closest_x = center.x.clamp(rect.x, rect.x + rect.width);
     let closest_y = center.y.clamp(rect.y, rect.y + rect.height);
 
     let distance_x = center.x - closest_x;
     let distance_y = center.y - closest_y;
 
-    let distance_squared = distance_x * distance_x + distance_y * distance_y;
-    distance_squared < radius * radius
-}
\ No newline at end of file
+    distance_x * distance_x + distance_y * distance_y
+}
+
+fn segment_distance_squared(point: Vector2, a: Vector2, b: Vector2) -> f32 {
+    let ab = b - a;
+    let length_squared = ab.dot(ab);
+    let t = if length_squared > 0.0 {
+        ((point - a).dot(ab) / length_squared).clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+    let closest = a + ab * t;
+    (point - closest).dot(point - closest)
+}
+
+/// Slab test: whether the segment `a`..`b` passes through `rect`.
+fn segment_crosses(a: Vector2, b: Vector2, rect: Rectangle) -> bool {
+    let (mut enter, mut exit) = (0.0_f32, 1.0_f32);
+    let axes = [
+        (a.x, b.x - a.x, rect.x, rect.x + rect.width),
+        (a.y, b.y - a.y, rect.y, rect.y + rect.height),
+    ];
+    for (start, delta, min, max) in axes {
+        if delta == 0.0 {
+            if start < min || start > max {
+                return false;
+            }
+            continue;
+        }
+        let (t_min, t_max) = ((min - start) / delta, (max - start) / delta);
+        enter = enter.max(t_min.min(t_max));
+        exit = exit.min(t_min.max(t_max));
+        if enter > exit {
+            return false;
+        }
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config;
+
+    const R: f32 = config::OBJ_RADIUS;
+
+    fn bucket_at(x: f32) -> Rectangle {
+        Rectangle::new(x, 400.0, config::BUCKET_W, config::BUCKET_H)
+    }
+
+    #[test]
+    fn max_speed_cat_cannot_jump_the_bucket() {
+        let bucket = bucket_at(100.0);
+        let x = bucket.x + bucket.width / 2.0;
+        let from = Vector2::new(x, bucket.y - R - 2.0);
+        let to = Vector2::new(x, from.y + config::OBJ_MAX_SPEED * 0.6);
+        assert!(to.y - R > bucket.y + bucket.height, "the step must clear the bucket");
+        assert!(!check_collision(from, R, bucket));
+        assert!(!check_collision(to, R, bucket));
+        assert!(check_swept_collision(from, to, R, bucket, bucket));
+    }
+
+    #[test]
+    fn long_frame_at_max_speed_is_caught() {
+        // A 250 ms frame with the cat falling and the bucket moving at full speed: the cat
+        // ends beside the bucket having passed through its corner on the way.
+        let dt = 0.25;
+        let before = bucket_at(100.0);
+        let after = bucket_at(100.0 + config::BUCKET_MAX_SPEED * dt);
+        let from = Vector2::new(after.x - R - 4.0, before.y - 40.0);
+        let to = Vector2::new(from.x, from.y + config::OBJ_MAX_SPEED * dt);
+        assert!(!check_collision(from, R, before));
+        assert!(!check_collision(to, R, after));
+        assert!(check_swept_collision(from, to, R, before, after));
+    }
+
+    #[test]
+    fn corner_graze_is_caught() {
+        let bucket = bucket_at(100.0);
+        let from = Vector2::new(bucket.x - 10.0, bucket.y - 2.0);
+        let to = Vector2::new(bucket.x + 2.0, bucket.y - 10.0);
+        assert!(!check_collision(from, R, bucket));
+        assert!(!check_collision(to, R, bucket));
+        assert!(check_swept_collision(from, to, R, bucket, bucket));
+    }
+
+    #[test]
+    fn near_miss_is_not_caught() {
+        let bucket = bucket_at(100.0);
+        let from = Vector2::new(bucket.x - 12.0, bucket.y - 4.0);
+        let to = Vector2::new(bucket.x + 4.0, bucket.y - 12.0);
+        assert!(!check_swept_collision(from, to, R, bucket, bucket));
+
+        let beside = bucket.x - R - 1.0;
+        let from = Vector2::new(beside, bucket.y - 50.0);
+        let to = Vector2::new(beside, bucket.y + bucket.height + 50.0);
+        assert!(!check_swept_collision(from, to, R, bucket, bucket));
+    }
+
+    #[test]
+    fn bucket_sliding_under_a_slow_cat_catches_it() {
+        let dt = 0.25;
+        let cat = Vector2::new(200.0, 420.0);
+        let to = Vector2::new(cat.x, cat.y + config::OBJ_BASE_SPEED * dt);
+        let before = bucket_at(cat.x - config::BUCKET_W - R - 2.0);
+        let after = bucket_at(before.x + config::BUCKET_MAX_SPEED * dt);
+        assert!(!check_collision(cat, R, before));
+        assert!(!check_collision(to, R, after));
+        assert!(check_swept_collision(cat, to, R, before, after));
+    }
+}
@@ -0,0 +1,116 @@
+use std::fmt;
+use std::time::{SystemTime, UNIX_EPOCH};
+use crate::config;
+use crate::rng::Rng;
+
+/// A calendar day in UTC, so every player gets the same challenge at the same time.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Date {
+    pub year: i32,
+    pub month: u32,
+    pub day: u32,
+}
+
+impl Date {
+    pub fn today() -> Self {
+        let secs = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        Self::from_days((secs / 86_400) as i64)
+    }
+
+    /// Days since 1970-01-01 to a civil date (Howard Hinnant's `civil_from_days`).
+    fn from_days(days: i64) -> Self {
+        let z = days + 719_468;
+        let era = z.div_euclid(146_097);
+        let doe = z - era * 146_097;
+        let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+        let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+        let mp = (5 * doy + 2) / 153;
+        let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+        let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+        let year = (yoe + era * 400 + if month <= 2 { 1 } else { 0 }) as i32;
+        Self { year, month, day }
+    }
+
+    /// Parses the `YYYY-MM-DD` form written by `Display`.
+    pub fn parse(text: &str) -> Option<Self> {
+        let mut parts = text.trim().splitn(3, '-');
+        let year = parts.next()?.parse().ok()?;
+        let month = parts.next()?.parse().ok()?;
+        let day = parts.next()?.parse().ok()?;
+        Some(Self { year, month, day })
+    }
+
+    fn key(self) -> u64 {
+        self.year as u64 * 10_000 + self.month as u64 * 100 + self.day as u64
+    }
+}
+
+impl fmt::Display for Date {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+/// Twists layered on top of a normal run. Only the daily challenge uses them for now.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Modifier {
+    DoubleDevils,
+    InvertedStart,
+    TinyBucket,
+    FastCats,
+}
+
+impl Modifier {
+    pub const ALL: [Modifier; 4] = [
+        Modifier::DoubleDevils,
+        Modifier::InvertedStart,
+        Modifier::TinyBucket,
+        Modifier::FastCats,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Modifier::DoubleDevils => "Double Devils",
+            Modifier::InvertedStart => "Inverted Start",
+            Modifier::TinyBucket => "Tiny Bucket",
+            Modifier::FastCats => "Fast Cats",
+        }
+    }
+}
+
+pub struct DailyChallenge {
+    pub date: Date,
+    pub seed: u64,
+    pub modifiers: Vec<Modifier>,
+}
+
+impl DailyChallenge {
+    /// Everything about the challenge comes from the date, so it is the same on every machine.
+    pub fn for_date(date: Date) -> Self {
+        let mut rng = Rng::new(date.key());
+        let seed = rng.next_u64();
+        let mut pool = Modifier::ALL.to_vec();
+        let mut modifiers = Vec::new();
+        while modifiers.len() < config::DAILY_MODIFIER_COUNT && !pool.is_empty() {
+            let index = rng.range_i32(0..=pool.len() as i32 - 1) as usize;
+            modifiers.push(pool.remove(index));
+        }
+        Self {
+            date,
+            seed,
+            modifiers,
+        }
+    }
+
+    pub fn today() -> Self {
+        Self::for_date(Date::today())
+    }
+
+    pub fn description(&self) -> String {
+        let names: Vec<&str> = self.modifiers.iter().map(|m| m.label()).collect();
+        format!("Daily {}: {}", self.date, names.join(" + "))
+    }
+}
@@ -1,7 +1,7 @@
-use raylib::prelude::*;
 use crate::config;
+use serde::Deserialize;
 
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, PartialEq, Eq, Deserialize)]
 pub enum DevilEffect {
     InvertControls,
     BucketSmall,
@@ -12,10 +12,39 @@ pub enum DevilEffect {
     ScoreTriple,
 }
 
+impl DevilEffect {
+    pub const ALL: [DevilEffect; 7] = [
+        DevilEffect::InvertControls,
+        DevilEffect::BucketSmall,
+        DevilEffect::BucketLarge,
+        DevilEffect::MusicSwap,
+        DevilEffect::BucketExplode,
+        DevilEffect::ScoreDouble,
+        DevilEffect::ScoreTriple,
+    ];
+
+    /// Short name, as typed in the debug console.
+    pub fn name(self) -> &'static str {
+        match self {
+            DevilEffect::InvertControls => "invert",
+            DevilEffect::BucketSmall => "small",
+            DevilEffect::BucketLarge => "large",
+            DevilEffect::MusicSwap => "music",
+            DevilEffect::BucketExplode => "explode",
+            DevilEffect::ScoreDouble => "double",
+            DevilEffect::ScoreTriple => "triple",
+        }
+    }
+}
+
 #[derive(Clone, Copy, PartialEq, Eq)]
 pub enum MusicMode {
     Default,
+    /// Index into the loaded devil-swap tracks.
     Alternate(usize),
+    Boss,
+    /// Played by the app on the menus; worlds never ask for it.
+    Menu,
 }
 
 pub struct EffectsState {
@@ -28,8 +57,6 @@ pub struct EffectsState {
     music_mode: MusicMode,
     message: String,
     message_timer: f32,
-    explode_timer: f32,
-    explode_pos: Vector2,
 }
 
 impl EffectsState {
@@ -44,8 +71,6 @@ impl EffectsState {
             music_mode: MusicMode::Default,
             message: String::new(),
             message_timer: 0.0,
-            explode_timer: 0.0,
-            explode_pos: Vector2::new(0.0, 0.0),
         }
     }
 
@@ -63,7 +88,6 @@ impl EffectsState {
         if self.message_timer <= 0.0 {
             self.message.clear();
         }
-        self.explode_timer = (self.explode_timer - dt).max(0.0);
     }
 
     pub fn apply_input(&self, move_x: f32) -> f32 {
@@ -74,6 +98,28 @@ impl EffectsState {
         }
     }
 
+    /// Whether any timed devil effect is still running.
+    pub fn any_active(&self) -> bool {
+        self.invert_timer > 0.0 || self.size_timer > 0.0 || self.multiplier_timer > 0.0
+    }
+
+    /// Every timer still running, by name, for the debug overlay.
+    pub fn timers(&self) -> Vec<(&'static str, f32)> {
+        [
+            ("invert", self.invert_timer),
+            ("size", self.size_timer),
+            ("multiplier", self.multiplier_timer),
+            ("message", self.message_timer),
+        ]
+        .into_iter()
+        .filter(|(_, time)| *time > 0.0)
+        .collect()
+    }
+
+    pub fn controls_inverted(&self) -> bool {
+        self.invert_timer > 0.0
+    }
+
     pub fn bucket_scale(&self) -> f32 {
         self.size_scale
     }
@@ -102,15 +148,6 @@ impl EffectsState {
         }
     }
 
-    pub fn explosion(&self) -> Option<(Vector2, f32)> {
-        if self.explode_timer > 0.0 {
-            let t = 1.0 - (self.explode_timer / config::DEVIL_EXPLODE_ANIM_DURATION);
-            Some((self.explode_pos, t.clamp(0.0, 1.0)))
-        } else {
-            None
-        }
-    }
-
     pub fn set_message(&mut self, text: &str) {
         self.message = text.to_string();
         self.message_timer = config::DEVIL_EFFECT_MESSAGE_DURATION;
@@ -120,6 +157,10 @@ impl EffectsState {
         self.invert_timer = config::DEVIL_INVERT_DURATION;
     }
 
+    pub fn apply_invert_for(&mut self, duration: f32) {
+        self.invert_timer = self.invert_timer.max(duration);
+    }
+
     pub fn apply_size_scale(&mut self, scale: f32) {
         self.size_scale = scale;
         self.size_timer = config::DEVIL_SIZE_EFFECT_DURATION;
@@ -137,9 +178,4 @@ impl EffectsState {
     pub fn set_music_default(&mut self) {
         self.music_mode = MusicMode::Default;
     }
-
-    pub fn trigger_explosion(&mut self, pos: Vector2) {
-        self.explode_pos = pos;
-        self.explode_timer = config::DEVIL_EXPLODE_ANIM_DURATION;
-    }
 }
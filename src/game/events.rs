@@ -0,0 +1,13 @@
+use crate::game::effects::DevilEffect;
+use crate::game::objects::CatKind;
+
+/// Things that happened during a `World::update`, for presentation layers (sound, and
+/// anything else that reacts to gameplay) to pick up with `World::take_events`.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum GameEvent {
+    Caught(CatKind),
+    Missed(CatKind),
+    DevilEffect(DevilEffect),
+    BucketExploded,
+    GiantCatLanded,
+}
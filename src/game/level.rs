@@ -0,0 +1,128 @@
+use serde::Deserialize;
+use std::path::Path;
+use crate::assets::Assets;
+use crate::config;
+use crate::game::effects::DevilEffect;
+use crate::game::objects::{CatKind, FallingObject, Motion};
+
+/// What a level asks of the player before it counts as cleared.
+#[derive(Clone, Copy, Deserialize)]
+pub enum LevelGoal {
+    Survive { seconds: f32 },
+    Score { points: i32 },
+}
+
+/// One cat in a level's timeline. `x` is a fraction of the screen width so levels do not
+/// depend on the window size.
+#[derive(Clone, Deserialize)]
+pub struct ScriptedSpawn {
+    pub time: f32,
+    pub kind: CatKind,
+    pub x: f32,
+    #[serde(default)]
+    pub motion: Motion,
+    #[serde(default)]
+    pub speed: Option<f32>,
+}
+
+/// A handcrafted level, read from a `.ron` file in `LEVELS_DIR`.
+#[derive(Clone, Deserialize)]
+pub struct Level {
+    #[serde(skip)]
+    pub id: String,
+    pub name: String,
+    pub goal: LevelGoal,
+    #[serde(default = "all_devil_effects")]
+    pub devil_effects: Vec<DevilEffect>,
+    #[serde(default)]
+    pub background: Option<String>,
+    #[serde(default)]
+    pub music: Option<String>,
+    pub spawns: Vec<ScriptedSpawn>,
+}
+
+fn all_devil_effects() -> Vec<DevilEffect> {
+    DevilEffect::ALL.to_vec()
+}
+
+impl Level {
+    pub fn load(assets: &Assets, relative: &str) -> Result<Self, String> {
+        let text = assets.read_to_string(relative).map_err(|e| e.to_string())?;
+        let mut level: Level = ron::from_str(&text).map_err(|e| e.to_string())?;
+        level.id = Path::new(relative)
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        level.spawns.sort_by(|a, b| a.time.total_cmp(&b.time));
+        Ok(level)
+    }
+
+    /// Every level in `LEVELS_DIR`, ordered by file name. Broken files are skipped with a
+    /// warning so one typo does not take the whole campaign down.
+    pub fn load_all(assets: &Assets) -> Vec<Self> {
+        let Ok(names) = assets.list(config::LEVELS_DIR) else {
+            eprintln!("warning: no levels found in {}", config::LEVELS_DIR);
+            return Vec::new();
+        };
+
+        let mut levels = Vec::new();
+        for name in names.iter().filter(|name| name.ends_with(".ron")) {
+            let path = format!("{}/{}", config::LEVELS_DIR, name);
+            match Self::load(assets, &path) {
+                Ok(level) => levels.push(level),
+                Err(err) => eprintln!("warning: skipping level {}: {}", path, err),
+            }
+        }
+        levels
+    }
+}
+
+/// Feeds a level's timeline into the world in place of the random `Spawner`.
+pub struct LevelScript {
+    spawns: Vec<ScriptedSpawn>,
+    next: usize,
+    time: f32,
+}
+
+impl LevelScript {
+    pub fn new(level: &Level) -> Self {
+        Self {
+            spawns: level.spawns.clone(),
+            next: 0,
+            time: 0.0,
+        }
+    }
+
+    pub fn update(&mut self, dt: f32, screen_w: f32) -> Vec<FallingObject> {
+        self.time += dt;
+        let mut spawned = Vec::new();
+        while let Some(spawn) = self.spawns.get(self.next) {
+            if spawn.time > self.time {
+                break;
+            }
+            let radius = config::OBJ_RADIUS;
+            let x = (spawn.x.clamp(0.0, 1.0) * screen_w).clamp(radius, screen_w - radius);
+            let speed = spawn
+                .speed
+                .unwrap_or(config::OBJ_BASE_SPEED * (1.0 + spawn.time * config::OBJ_SPEED_SCALE));
+            spawned.push(FallingObject::new(x, spawn.kind, speed).with_motion(spawn.motion));
+            self.next += 1;
+        }
+        spawned
+    }
+
+    pub fn finished(&self) -> bool {
+        self.next >= self.spawns.len()
+    }
+}
+
+/// Clearing a level earns at least one star; the calmer the cat at the end, the more.
+pub fn stars_for(anger_fraction: f32) -> u8 {
+    if anger_fraction <= config::LEVEL_THREE_STAR_ANGER {
+        3
+    } else if anger_fraction <= config::LEVEL_TWO_STAR_ANGER {
+        2
+    } else {
+        1
+    }
+}
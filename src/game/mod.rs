@@ -1,7 +1,19 @@
 pub mod world;
+pub mod animator;
 pub mod bucket;
 pub mod objects;
+pub mod particles;
 pub mod spawn;
 pub mod collision;
 pub mod scoring;
 pub mod effects;
+pub mod autopilot;
+pub mod rules;
+pub mod anger;
+pub mod mode;
+pub mod daily;
+pub mod level;
+pub mod boss;
+pub mod player;
+pub mod versus;
+pub mod events;
@@ -0,0 +1,166 @@
+use crate::config;
+use crate::game::daily::{DailyChallenge, Modifier};
+use crate::game::level::Level;
+use crate::game::player::PlayerStats;
+use crate::game::rules::RuleSet;
+use crate::rng;
+
+/// What ends a run and what the leaderboard ranks it by.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum GameMode {
+    Endless,
+    TimeAttack { seconds: u32 },
+    Target { points: i32 },
+    SuddenDeath,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum RunEnd {
+    CatLanded,
+    TimeUp,
+    TargetReached,
+    LevelCleared,
+    OutOfCats,
+    Quit,
+}
+
+#[derive(Clone)]
+pub struct RunResult {
+    pub mode: GameMode,
+    pub score: i32,
+    pub elapsed: f32,
+    pub end: RunEnd,
+    pub stars: Option<u8>,
+    pub players: Vec<PlayerStats>,
+}
+
+/// Everything needed to start a run. The same setup and seed always play out the same way.
+#[derive(Clone)]
+pub struct RunSetup {
+    pub rules: RuleSet,
+    pub mode: GameMode,
+    pub seed: u64,
+    pub modifiers: Vec<Modifier>,
+    pub intro: Option<String>,
+    pub level: Option<Level>,
+    pub players: usize,
+    /// Devils caught in this world are sent to a rival world instead of applied here.
+    pub versus: bool,
+}
+
+impl RunSetup {
+    pub fn new(rules: RuleSet, mode: GameMode) -> Self {
+        Self {
+            rules,
+            mode,
+            seed: rng::seed_from_clock(),
+            modifiers: Vec::new(),
+            intro: None,
+            level: None,
+            players: 1,
+            versus: false,
+        }
+    }
+
+    pub fn daily(challenge: &DailyChallenge) -> Self {
+        Self {
+            rules: RuleSet::AngryBar,
+            mode: GameMode::Endless,
+            seed: challenge.seed,
+            modifiers: challenge.modifiers.clone(),
+            intro: Some(challenge.description()),
+            level: None,
+            players: 1,
+            versus: false,
+        }
+    }
+
+    pub fn level(level: &Level) -> Self {
+        Self {
+            intro: Some(level.name.clone()),
+            level: Some(level.clone()),
+            ..Self::new(RuleSet::AngryBar, GameMode::Endless)
+        }
+    }
+
+    /// One side of a split-screen match. Both sides share the setup, seed included.
+    pub fn versus(rules: RuleSet, mode: GameMode) -> Self {
+        Self {
+            versus: true,
+            ..Self::new(rules, mode)
+        }
+    }
+}
+
+impl GameMode {
+    pub const ALL: [GameMode; 5] = [
+        GameMode::Endless,
+        GameMode::TimeAttack {
+            seconds: config::TIME_ATTACK_SHORT,
+        },
+        GameMode::TimeAttack {
+            seconds: config::TIME_ATTACK_LONG,
+        },
+        GameMode::Target {
+            points: config::TARGET_POINTS,
+        },
+        GameMode::SuddenDeath,
+    ];
+
+    pub fn label(self) -> String {
+        match self {
+            GameMode::Endless => "Endless".to_string(),
+            GameMode::TimeAttack { seconds } => format!("Time Attack {}s", seconds),
+            GameMode::Target { points } => format!("Target {}", points),
+            GameMode::SuddenDeath => "Sudden Death".to_string(),
+        }
+    }
+
+    pub fn next(self) -> Self {
+        Self::cycle(self, 1)
+    }
+
+    fn cycle(self, step: usize) -> Self {
+        let idx = Self::ALL.iter().position(|m| *m == self).unwrap_or(0);
+        Self::ALL[(idx + step) % Self::ALL.len()]
+    }
+
+    /// Each rule set ranks apart. Angry Bar keeps the original file names so existing high
+    /// scores carry over.
+    pub fn scores_file(self, rules: RuleSet) -> String {
+        let file = match self {
+            GameMode::Endless => config::SCORES_FILE.to_string(),
+            GameMode::TimeAttack { seconds } => format!("scores_time_{}.txt", seconds),
+            GameMode::Target { points } => format!("scores_target_{}.txt", points),
+            GameMode::SuddenDeath => "scores_sudden_death.txt".to_string(),
+        };
+        match rules {
+            RuleSet::AngryBar => file,
+            RuleSet::ClassicLives => file.replacen("scores", "scores_lives", 1),
+        }
+    }
+
+    /// Target runs are ranked by how fast the goal was reached, in milliseconds.
+    pub fn lower_is_better(self) -> bool {
+        matches!(self, GameMode::Target { .. })
+    }
+
+    pub fn format_value(self, value: i32) -> String {
+        match self {
+            GameMode::Target { .. } => format!("{:.2}s", value as f32 / 1000.0),
+            _ => value.to_string(),
+        }
+    }
+}
+
+impl RunResult {
+    /// The value stored on this mode's leaderboard, or `None` if the run does not qualify.
+    pub fn ranked_value(&self) -> Option<i32> {
+        match self.mode {
+            GameMode::Target { .. } => {
+                (self.end == RunEnd::TargetReached).then(|| (self.elapsed * 1000.0).round() as i32)
+            }
+            _ => Some(self.score),
+        }
+    }
+}
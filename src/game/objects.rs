@@ -1,61 +1,276 @@
 use crate::config;
+use crate::game::animator::{AnimEvent, Animator, Clip, FrameTimes, PlayMode};
+use crate::render::atlas::SpriteId;
 use raylib::prelude::*;
+use serde::Deserialize;
+use std::f32::consts::TAU;
 
-#[derive(Clone, Copy)]
+static FALL: Clip = Clip {
+    name: "fall",
+    frames: 12,
+    fps: config::OBJ_ANIM_FPS,
+    mode: PlayMode::Loop,
+    events: &[],
+};
+static SQUASH: Clip = Clip {
+    name: "squash",
+    frames: 4,
+    fps: config::CAT_SQUASH_FPS,
+    mode: PlayMode::Once,
+    events: &[(3, AnimEvent::Vanish)],
+};
+
+#[derive(Clone, Copy, PartialEq, Eq, Deserialize)]
 pub enum CatKind {
     Normal,
     Angel,
     Devil,
     Explode,
+    Golden,
+    Ghost,
+    Splitting,
+    Bomb,
+    Heart,
+}
+
+impl CatKind {
+    pub const ALL: [CatKind; 9] = [
+        CatKind::Normal,
+        CatKind::Angel,
+        CatKind::Devil,
+        CatKind::Explode,
+        CatKind::Golden,
+        CatKind::Ghost,
+        CatKind::Splitting,
+        CatKind::Bomb,
+        CatKind::Heart,
+    ];
+
+    /// Lower-case name, as typed in the debug console.
+    pub fn name(self) -> &'static str {
+        match self {
+            CatKind::Normal => "normal",
+            CatKind::Angel => "angel",
+            CatKind::Devil => "devil",
+            CatKind::Explode => "explode",
+            CatKind::Golden => "golden",
+            CatKind::Ghost => "ghost",
+            CatKind::Splitting => "splitting",
+            CatKind::Bomb => "bomb",
+            CatKind::Heart => "heart",
+        }
+    }
+
+    /// The sheet this kind is drawn from. Hearts have no sheet of their own and never show
+    /// one, so they borrow the normal cat's.
+    pub fn sprite(self) -> SpriteId {
+        match self {
+            CatKind::Angel => SpriteId::AngelCat,
+            CatKind::Devil | CatKind::Explode | CatKind::Bomb => SpriteId::DevilCat,
+            CatKind::Normal
+            | CatKind::Golden
+            | CatKind::Ghost
+            | CatKind::Splitting
+            | CatKind::Heart => SpriteId::NormalCat,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Default, Deserialize)]
+pub enum Motion {
+    #[default]
+    Straight,
+    Drift { speed: f32 },
+    Sine { amplitude: f32, frequency: f32 },
+    Bounce { speed: f32 },
+    Parachute,
+}
+
+#[derive(Clone, Copy)]
+pub struct Landing {
+    pub pos: Vector2,
+    pub time: f32,
 }
 
 #[derive(Clone)]
 pub struct FallingObject {
     pub pos: Vector2,
+    /// Where the last `update` started from, for swept collision.
+    prev_pos: Vector2,
     pub radius: f32,
     velocity: Vector2,
     accel: Vector2,
+    max_speed: f32,
+    motion: Motion,
+    origin_x: f32,
     kind: CatKind,
-    anim_time: f32,
-    anim_frame: usize,
+    scale: f32,
+    age: f32,
+    anim: Animator,
 }
 
 impl FallingObject {
     pub fn new(x: f32, kind: CatKind, initial_speed: f32) -> Self {
+        let (initial_speed, max_speed) = match kind {
+            CatKind::Golden => (
+                initial_speed * config::GOLDEN_SPEED_MULT,
+                config::GOLDEN_MAX_SPEED,
+            ),
+            _ => (initial_speed, config::OBJ_MAX_SPEED),
+        };
         Self {
             pos: Vector2::new(x, -10.0),
+            prev_pos: Vector2::new(x, -10.0),
             radius: config::OBJ_RADIUS,
             velocity: Vector2::new(0.0, initial_speed),
             accel: Vector2::new(0.0, config::OBJ_GRAVITY),
+            max_speed,
+            motion: Motion::Straight,
+            origin_x: x,
             kind,
-            anim_time: 0.0,
-            anim_frame: 0,
+            scale: 1.0,
+            age: 0.0,
+            anim: Animator::new(kind.sprite(), &FALL),
+        }
+    }
+
+    pub fn with_motion(mut self, motion: Motion) -> Self {
+        self.motion = motion;
+        self.origin_x = self.pos.x;
+        match motion {
+            Motion::Drift { speed } | Motion::Bounce { speed } => self.velocity.x = speed,
+            Motion::Parachute => {
+                self.max_speed = self.max_speed.min(config::PARACHUTE_MAX_SPEED);
+                self.velocity.y = self.velocity.y.min(self.max_speed);
+            }
+            Motion::Straight | Motion::Sine { .. } => {}
         }
+        self
+    }
+
+    pub fn with_start_y(mut self, y: f32) -> Self {
+        self.pos.y = y;
+        self.prev_pos = self.pos;
+        self
     }
 
-    pub fn update(&mut self, dt: f32) {
+    pub fn update(&mut self, dt: f32, screen_w: f32) {
+        self.prev_pos = self.pos;
+        self.age += dt;
         self.velocity.y += self.accel.y * dt;
-        self.velocity.y = self.velocity.y.min(config::OBJ_MAX_SPEED);
+        self.velocity.y = self.velocity.y.min(self.max_speed);
         self.pos.y += self.velocity.y * dt;
-        if config::OBJ_FRAME_COUNT > 1 {
-            let frame_dt = 1.0 / config::OBJ_ANIM_FPS.max(1.0);
-            self.anim_time += dt;
-            while self.anim_time >= frame_dt {
-                self.anim_time -= frame_dt;
-                self.anim_frame = (self.anim_frame + 1) % config::OBJ_FRAME_COUNT;
+
+        match self.motion {
+            Motion::Straight => {}
+            Motion::Drift { .. } | Motion::Bounce { .. } => {
+                self.velocity.x += self.accel.x * dt;
+                self.pos.x += self.velocity.x * dt;
+            }
+            Motion::Sine {
+                amplitude,
+                frequency,
+            } => {
+                self.pos.x = self.origin_x + amplitude * (self.age * frequency * TAU).sin();
             }
+            Motion::Parachute => {
+                self.pos.x = self.origin_x
+                    + config::PARACHUTE_SWAY * (self.age * config::PARACHUTE_SWAY_FREQ * TAU).sin();
+            }
+        }
+
+        let min_x = self.radius;
+        let max_x = (screen_w - self.radius).max(min_x);
+        if self.pos.x < min_x || self.pos.x > max_x {
+            self.pos.x = self.pos.x.clamp(min_x, max_x);
+            if let Motion::Bounce { .. } = self.motion {
+                self.velocity.x = -self.velocity.x;
+            } else {
+                self.velocity.x = 0.0;
+            }
+        }
+
+        self.anim.advance(dt);
+    }
+
+    /// Steps a copy of this object forward until it reaches `target_y`, returning where and
+    /// how soon it gets there. `None` if it would take longer than `PREDICT_MAX_TIME`.
+    pub fn predict_landing(&self, target_y: f32, screen_w: f32) -> Option<Landing> {
+        let mut probe = self.clone();
+        let mut time = 0.0;
+        while probe.pos.y < target_y {
+            if time >= config::PREDICT_MAX_TIME {
+                return None;
+            }
+            probe.update(config::PREDICT_STEP, screen_w);
+            time += config::PREDICT_STEP;
         }
+        Some(Landing {
+            pos: probe.pos,
+            time,
+        })
+    }
+
+    pub fn prev_pos(&self) -> Vector2 {
+        self.prev_pos
     }
 
     pub fn offscreen(&self, screen_h: f32) -> bool {
         self.pos.y - self.radius > screen_h
     }
 
-    pub fn frame(&self) -> usize {
-        self.anim_frame
+    pub fn animator(&self) -> &Animator {
+        &self.anim
+    }
+
+    /// Stops falling and plays the squash into the bucket.
+    pub fn catch(&mut self) {
+        self.anim.play(&SQUASH);
+    }
+
+    /// Animation only, for a caught cat that is just being shown.
+    pub fn animate(&mut self, dt: f32, times: &FrameTimes) -> Vec<AnimEvent> {
+        self.anim.update(dt, times)
     }
 
     pub fn kind(&self) -> CatKind {
         self.kind
     }
+
+    pub fn scale(&self) -> f32 {
+        self.scale
+    }
+
+    /// Ghost cats fade out for part of every flicker period; everything else is always shown.
+    pub fn visible(&self) -> bool {
+        if self.kind != CatKind::Ghost {
+            return true;
+        }
+        let phase = (self.age % config::GHOST_FLICKER_PERIOD) / config::GHOST_FLICKER_PERIOD;
+        phase < 1.0 - config::GHOST_HIDDEN_FRACTION
+    }
+
+    pub fn should_split(&self, screen_h: f32) -> bool {
+        self.kind == CatKind::Splitting && self.pos.y >= screen_h * config::SPLIT_HEIGHT_FRACTION
+    }
+
+    /// Breaks a splitting cat into two small normal cats either side of it.
+    pub fn split(&self) -> [FallingObject; 2] {
+        let make_child = |offset: f32| {
+            let mut child = self.clone();
+            child.kind = CatKind::Normal;
+            child.scale = config::SPLIT_CHILD_SCALE;
+            child.radius = self.radius * config::SPLIT_CHILD_SCALE;
+            child.pos.x += offset;
+            // Sweep from where the parent was, so the frame it splits still covers its path.
+            child.prev_pos = self.prev_pos;
+            child.with_motion(Motion::Drift {
+                speed: offset.signum() * config::SPLIT_CHILD_DRIFT,
+            })
+        };
+        [
+            make_child(-config::SPLIT_CHILD_OFFSET),
+            make_child(config::SPLIT_CHILD_OFFSET),
+        ]
+    }
 }
@@ -0,0 +1,278 @@
+use std::f32::consts::{FRAC_PI_2, TAU};
+use raylib::prelude::*;
+use crate::config;
+use crate::game::objects::CatKind;
+use crate::render::atlas::SpriteId;
+use crate::rng::Rng;
+
+/// How a particle is drawn.
+#[derive(Clone, Copy, PartialEq)]
+pub enum Shape {
+    Spark,
+    Smoke,
+    Heart,
+    Tear,
+    /// The first frame of a sprite, scaled to the particle's size.
+    Sprite(SpriteId),
+}
+
+/// The recipe for one kind of particle. Bursts emit `count` at once; streams emit `rate`
+/// a second for as long as they are fed. Pairs are (min, max) for random values and
+/// (start, end) for values that change over a particle's life.
+pub struct Emitter {
+    pub shape: Shape,
+    pub count: usize,
+    pub rate: f32,
+    pub lifetime: (f32, f32),
+    pub speed: (f32, f32),
+    /// Radians, 0 pointing right; positive y is down the screen.
+    pub direction: f32,
+    pub spread: f32,
+    pub gravity: f32,
+    pub size: (f32, f32),
+    pub color: (Color, Color),
+    /// Most degrees a second a particle may turn, either way.
+    pub spin: f32,
+}
+
+pub static CATCH_SPARKS: Emitter = Emitter {
+    shape: Shape::Spark,
+    count: 14,
+    rate: 0.0,
+    lifetime: (0.3, 0.6),
+    speed: (80.0, 200.0),
+    direction: -FRAC_PI_2,
+    spread: 2.4,
+    gravity: 300.0,
+    size: (4.0, 1.0),
+    color: (Color::WHITE, Color::new(255, 255, 255, 0)),
+    spin: 360.0,
+};
+
+pub static DEVIL_SMOKE: Emitter = Emitter {
+    shape: Shape::Smoke,
+    count: 0,
+    rate: 30.0,
+    lifetime: (0.5, 0.9),
+    speed: (10.0, 30.0),
+    direction: -FRAC_PI_2,
+    spread: 1.2,
+    gravity: -40.0,
+    size: (4.0, 12.0),
+    color: (Color::new(70, 40, 70, 150), Color::new(70, 40, 70, 0)),
+    spin: 0.0,
+};
+
+pub static EXPLOSION_FIRE: Emitter = Emitter {
+    shape: Shape::Spark,
+    count: 40,
+    rate: 0.0,
+    lifetime: (0.3, 0.7),
+    speed: (150.0, 380.0),
+    direction: 0.0,
+    spread: TAU,
+    gravity: 200.0,
+    size: (6.0, 2.0),
+    color: (Color::new(255, 225, 120, 255), Color::new(230, 60, 40, 0)),
+    spin: 540.0,
+};
+
+pub static EXPLOSION_SMOKE: Emitter = Emitter {
+    shape: Shape::Smoke,
+    count: 16,
+    rate: 0.0,
+    lifetime: (0.6, 1.2),
+    speed: (20.0, 90.0),
+    direction: 0.0,
+    spread: TAU,
+    gravity: -30.0,
+    size: (10.0, 28.0),
+    color: (Color::new(90, 80, 90, 180), Color::new(90, 80, 90, 0)),
+    spin: 0.0,
+};
+
+pub static EXPLOSION_DEBRIS: Emitter = Emitter {
+    shape: Shape::Sprite(SpriteId::Bucket),
+    count: 6,
+    rate: 0.0,
+    lifetime: (0.8, 1.2),
+    speed: (150.0, 300.0),
+    direction: -FRAC_PI_2,
+    spread: 1.6,
+    gravity: 700.0,
+    size: (14.0, 10.0),
+    color: (Color::WHITE, Color::new(255, 255, 255, 0)),
+    spin: 720.0,
+};
+
+pub static ANGEL_HEARTS: Emitter = Emitter {
+    shape: Shape::Heart,
+    count: 6,
+    rate: 0.0,
+    lifetime: (0.8, 1.3),
+    speed: (30.0, 80.0),
+    direction: -FRAC_PI_2,
+    spread: 1.0,
+    gravity: -20.0,
+    size: (10.0, 16.0),
+    color: (config::COLOR_HEART, Color::new(235, 70, 120, 0)),
+    spin: 0.0,
+};
+
+pub static GIANT_CAT_TEARS: Emitter = Emitter {
+    shape: Shape::Tear,
+    count: 0,
+    rate: 12.0,
+    lifetime: (0.8, 1.4),
+    speed: (60.0, 140.0),
+    direction: -FRAC_PI_2,
+    spread: 2.2,
+    gravity: 500.0,
+    size: (7.0, 4.0),
+    color: (config::COLOR_TEAR, Color::new(120, 190, 255, 0)),
+    spin: 0.0,
+};
+
+/// What a catch sparkles with.
+pub fn catch_color(kind: CatKind) -> Color {
+    match kind {
+        CatKind::Normal => config::COLOR_SPARK_NORMAL,
+        CatKind::Angel => config::COLOR_SPARK_ANGEL,
+        CatKind::Devil | CatKind::Explode => config::COLOR_SPARK_DEVIL,
+        CatKind::Golden => config::COLOR_GOLDEN_CAT,
+        CatKind::Ghost => config::COLOR_GHOST_CAT,
+        CatKind::Splitting => config::COLOR_SPLIT_CAT,
+        CatKind::Bomb => config::COLOR_SPARK_BOMB,
+        CatKind::Heart => config::COLOR_HEART,
+    }
+}
+
+#[derive(Clone, Copy)]
+pub struct Particle {
+    pos: Vector2,
+    vel: Vector2,
+    age: f32,
+    lifetime: f32,
+    gravity: f32,
+    size: (f32, f32),
+    color: (Color, Color),
+    shape: Shape,
+    rotation: f32,
+    spin: f32,
+}
+
+impl Particle {
+    fn life(&self) -> f32 {
+        (self.age / self.lifetime).clamp(0.0, 1.0)
+    }
+
+    pub fn pos(&self) -> Vector2 {
+        self.pos
+    }
+
+    pub fn size(&self) -> f32 {
+        self.size.0 + (self.size.1 - self.size.0) * self.life()
+    }
+
+    pub fn color(&self) -> Color {
+        let t = self.life();
+        let mix = |a: u8, b: u8| (a as f32 + (b as f32 - a as f32) * t) as u8;
+        let (from, to) = self.color;
+        Color::new(mix(from.r, to.r), mix(from.g, to.g), mix(from.b, to.b), mix(from.a, to.a))
+    }
+
+    pub fn shape(&self) -> Shape {
+        self.shape
+    }
+
+    pub fn rotation(&self) -> f32 {
+        self.rotation
+    }
+}
+
+/// Every live particle in one world. The pool is allocated once; when it is full new
+/// particles are dropped rather than growing it. Has its own random stream so the
+/// simulation plays out the same with or without particles.
+pub struct ParticleSystem {
+    particles: Vec<Particle>,
+    rng: Rng,
+}
+
+impl ParticleSystem {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            particles: Vec::with_capacity(config::PARTICLE_POOL_SIZE),
+            rng: Rng::new(seed),
+        }
+    }
+
+    pub fn update(&mut self, dt: f32) {
+        for p in &mut self.particles {
+            p.age += dt;
+            p.vel.y += p.gravity * dt;
+            p.pos += p.vel * dt;
+            p.rotation += p.spin * dt;
+        }
+        self.particles.retain(|p| p.age < p.lifetime);
+    }
+
+    pub fn particles(&self) -> &[Particle] {
+        &self.particles
+    }
+
+    pub fn burst(&mut self, emitter: &Emitter, pos: Vector2) {
+        for _ in 0..emitter.count {
+            self.emit(emitter, pos, None);
+        }
+    }
+
+    /// A burst in `color` instead of the emitter's own, fading out the same way.
+    pub fn burst_tinted(&mut self, emitter: &Emitter, pos: Vector2, color: Color) {
+        for _ in 0..emitter.count {
+            self.emit(emitter, pos, Some(color));
+        }
+    }
+
+    /// Feeds a continuous emitter for `dt`. Fractions of a particle carry over by chance,
+    /// so low rates still come out right on average at any frame rate.
+    pub fn stream(&mut self, emitter: &Emitter, pos: Vector2, dt: f32) {
+        let count = (emitter.rate * dt + self.rng.unit()) as usize;
+        for _ in 0..count {
+            self.emit(emitter, pos, None);
+        }
+    }
+
+    pub fn explosion(&mut self, pos: Vector2) {
+        self.burst(&EXPLOSION_SMOKE, pos);
+        self.burst(&EXPLOSION_FIRE, pos);
+        self.burst(&EXPLOSION_DEBRIS, pos);
+    }
+
+    fn emit(&mut self, emitter: &Emitter, pos: Vector2, tint: Option<Color>) {
+        if self.particles.len() >= config::PARTICLE_POOL_SIZE {
+            return;
+        }
+        let mut between = |(min, max): (f32, f32)| min + (max - min) * self.rng.unit();
+        let angle = emitter.direction + emitter.spread * (between((0.0, 1.0)) - 0.5);
+        let speed = between(emitter.speed);
+        let lifetime = between(emitter.lifetime).max(0.01);
+        let rotation = between((0.0, 360.0));
+        let spin = between((-emitter.spin, emitter.spin));
+        let color = match tint {
+            Some(color) => (color, color.alpha(0.0)),
+            None => emitter.color,
+        };
+        self.particles.push(Particle {
+            pos,
+            vel: Vector2::new(angle.cos(), angle.sin()) * speed,
+            age: 0.0,
+            lifetime,
+            gravity: emitter.gravity,
+            size: emitter.size,
+            color,
+            shape: emitter.shape,
+            rotation,
+            spin,
+        });
+    }
+}
@@ -0,0 +1,62 @@
+use raylib::prelude::*;
+use crate::game::bucket::Bucket;
+use crate::game::effects::EffectsState;
+use crate::game::objects::CatKind;
+
+#[derive(Clone, Copy, Default)]
+pub struct PlayerStats {
+    pub caught: i32,
+    pub angels: i32,
+    pub devils: i32,
+    pub points: i32,
+}
+
+impl PlayerStats {
+    pub fn record(&mut self, kind: CatKind, points: i32) {
+        self.caught += 1;
+        self.points += points;
+        match kind {
+            CatKind::Angel => self.angels += 1,
+            CatKind::Devil | CatKind::Explode | CatKind::Bomb => self.devils += 1,
+            _ => {}
+        }
+    }
+}
+
+/// One bucket and the devil effects aimed at it. Score and anger stay shared in `World`.
+pub struct Player {
+    pub bucket: Bucket,
+    pub effects: EffectsState,
+    pub stats: PlayerStats,
+    bucket_scale: f32,
+}
+
+impl Player {
+    pub fn new(screen_w: f32, screen_h: f32) -> Self {
+        Self {
+            bucket: Bucket::new(screen_w, screen_h),
+            effects: EffectsState::new(),
+            stats: PlayerStats::default(),
+            bucket_scale: 1.0,
+        }
+    }
+
+    /// Resizes the bucket when a size effect starts or wears off.
+    pub fn update_size(&mut self, base_size: Vector2, scale: f32, screen_w: f32, screen_h: f32) {
+        let desired_scale = self.effects.bucket_scale() * scale;
+        if (desired_scale - self.bucket_scale).abs() > f32::EPSILON {
+            self.bucket_scale = desired_scale;
+            let size = Vector2::new(base_size.x * desired_scale, base_size.y * desired_scale);
+            self.bucket.apply_size(size, screen_w, screen_h);
+        }
+    }
+
+    pub fn reset_scale(&mut self) {
+        self.bucket_scale = 1.0;
+    }
+
+    pub fn bucket_center(&self) -> Vector2 {
+        let rect = self.bucket.rect();
+        Vector2::new(rect.x + rect.width / 2.0, rect.y + rect.height / 2.0)
+    }
+}
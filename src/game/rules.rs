@@ -0,0 +1,24 @@
+/// How a run is lost: the hidden-budget angry bar, or a visible stack of hearts.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum RuleSet {
+    AngryBar,
+    ClassicLives,
+}
+
+impl RuleSet {
+    pub const ALL: [RuleSet; 2] = [RuleSet::AngryBar, RuleSet::ClassicLives];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            RuleSet::AngryBar => "Angry Bar",
+            RuleSet::ClassicLives => "Classic Lives",
+        }
+    }
+
+    pub fn next(self) -> Self {
+        match self {
+            RuleSet::AngryBar => RuleSet::ClassicLives,
+            RuleSet::ClassicLives => RuleSet::AngryBar,
+        }
+    }
+}
@@ -1,6 +1,6 @@
 use crate::config::*;
 use crate::game::objects::CatKind;
-use raylib::prelude::*;
+use crate::rng::Rng;
 
 pub struct Scoring {
     score: i32,
@@ -11,18 +11,27 @@ impl Scoring {
         Self { score: 0 }
     }
 
-    pub fn register_catch(&mut self, kind: CatKind, rl: &RaylibHandle, multiplier: i32) -> i32 {
+    pub fn register_catch(&mut self, kind: CatKind, rng: &mut Rng, multiplier: i32) -> i32 {
         let delta = match kind {
             CatKind::Normal => NORMAL_CAT_SCORE,
-            CatKind::Angel => rl.get_random_value::<i32>(2..6),
+            CatKind::Angel => rng.range_i32(2..=6),
             CatKind::Devil => DEVIL_CAT_SCORE,
             CatKind::Explode => DEVIL_CAT_SCORE,
+            CatKind::Golden => GOLDEN_CAT_SCORE,
+            CatKind::Ghost => GHOST_CAT_SCORE,
+            CatKind::Splitting => NORMAL_CAT_SCORE,
+            CatKind::Bomb => BOMB_CAT_SCORE,
+            CatKind::Heart => HEART_PICKUP_SCORE,
         };
         let total = delta * multiplier.max(1);
         self.score = (self.score + total).max(0);
         total
     }
 
+    pub fn add_bonus(&mut self, points: i32) {
+        self.score = (self.score + points).max(0);
+    }
+
     pub fn apply_multiplier(&mut self, multiplier: i32) {
         let mult = multiplier.max(1);
         self.score = (self.score * mult).max(0);
@@ -1,28 +1,55 @@
-use raylib::prelude::*;
 use crate::config;
-use crate::game::objects::{CatKind, FallingObject};
+use crate::game::anger::AngerStage;
+use crate::game::objects::{CatKind, FallingObject, Motion};
+use crate::game::daily::Modifier;
+use crate::game::level::LevelScript;
+use crate::game::rules::RuleSet;
+use crate::rng::Rng;
+
+/// Where a run's cats come from: the endless random spawner or a level's fixed timeline.
+pub enum SpawnSource {
+    Random(Spawner),
+    Script(LevelScript),
+}
 
 pub struct Spawner {
     timer: f32,
     elapsed_time: f32,
     devil_cooldown: f32,
+    rules: RuleSet,
+    devil_scale: f32,
+    speed_scale: f32,
 }
 
 impl Spawner {
-    pub fn new() -> Self {
+    pub fn new(rules: RuleSet, modifiers: &[Modifier]) -> Self {
+        let devil_scale = if modifiers.contains(&Modifier::DoubleDevils) {
+            2.0
+        } else {
+            1.0
+        };
+        let speed_scale = if modifiers.contains(&Modifier::FastCats) {
+            config::DAILY_FAST_CATS_SCALE
+        } else {
+            1.0
+        };
         Self {
             timer: 0.0,
             elapsed_time: 0.0,
             devil_cooldown: 0.0,
+            rules,
+            devil_scale,
+            speed_scale,
         }
     }
 
     pub fn update(
         &mut self,
-        rl: &RaylibHandle,
+        rng: &mut Rng,
         dt: f32,
         screen_w: f32,
         elapsed_time: f32,
+        stage: AngerStage,
     ) -> Option<FallingObject> {
         self.timer += dt;
         self.elapsed_time += dt;
@@ -34,29 +61,96 @@ impl Spawner {
         }
 
         self.timer = 0.0;
-        let x = rl.get_random_value::<i32>(20..(screen_w as i32 - 20)) as f32;
+        let x = rng.range_i32(20..=(screen_w as i32 - 20)) as f32;
+        let roll = rng.unit();
+        let mut threshold = 0.0;
+        let mut kind = CatKind::Normal;
+        for (candidate, rate) in self.rates(stage) {
+            threshold += rate;
+            if roll < threshold {
+                kind = candidate;
+                break;
+            }
+        }
+        if kind == CatKind::Devil {
+            self.devil_cooldown = config::DEVIL_COOLDOWN;
+        }
+        let difficulty = 1.0 + elapsed_time * config::OBJ_SPEED_SCALE;
+        let initial_speed = config::OBJ_BASE_SPEED * difficulty * self.speed_scale;
+        let motion = pick_motion(rng, kind, self.elapsed_time);
+        Some(FallingObject::new(x, kind, initial_speed).with_motion(motion))
+    }
+
+    /// Chance of each special cat on the next spawn; anything left over is a normal cat.
+    pub fn rates(&self, stage: AngerStage) -> [(CatKind, f32); 7] {
         let devil_allowed = self.devil_cooldown <= 0.0;
         let devil_rate = if devil_allowed {
-            devil_rate(self.elapsed_time)
+            devil_rate(self.elapsed_time) * stage.devil_rate_scale() * self.devil_scale
         } else {
             0.0
         };
-        let angel_rate = config::ANGEL_RATE;
-        let normal_rate = (1.0 - angel_rate - devil_rate).max(0.0);
-        let roll = rl.get_random_value::<i32>(0..1000) as f32 / 1000.0;
-        let kind = if roll < devil_rate {
-            self.devil_cooldown = config::DEVIL_COOLDOWN;
-            CatKind::Devil
-        } else if roll < devil_rate + angel_rate {
-            CatKind::Angel
-        } else if roll < devil_rate + angel_rate + normal_rate {
-            CatKind::Normal
+        let bomb_rate = if self.elapsed_time >= config::BOMB_START_TIME {
+            config::BOMB_RATE * stage.devil_rate_scale()
         } else {
-            CatKind::Normal
+            0.0
         };
-        let difficulty = 1.0 + elapsed_time * config::OBJ_SPEED_SCALE;
-        let initial_speed = config::OBJ_BASE_SPEED * difficulty;
-        Some(FallingObject::new(x, kind, initial_speed))
+        let heart_rate = match self.rules {
+            RuleSet::ClassicLives => config::HEART_RATE,
+            RuleSet::AngryBar => 0.0,
+        };
+        [
+            (CatKind::Devil, devil_rate),
+            (CatKind::Angel, config::ANGEL_RATE),
+            (CatKind::Golden, config::GOLDEN_RATE),
+            (CatKind::Ghost, config::GHOST_RATE),
+            (CatKind::Splitting, config::SPLIT_RATE),
+            (CatKind::Bomb, bomb_rate),
+            (CatKind::Heart, heart_rate),
+        ]
+    }
+}
+
+fn random_between(rng: &mut Rng, min: f32, max: f32) -> f32 {
+    min + (max - min) * rng.unit()
+}
+
+fn random_sign(rng: &mut Rng) -> f32 {
+    if rng.unit() < 0.5 { -1.0 } else { 1.0 }
+}
+
+/// Special cats keep a signature motion; everything else starts straight and picks up
+/// drift, wobble and bounce more often as the run goes on.
+fn pick_motion(rng: &mut Rng, kind: CatKind, elapsed_time: f32) -> Motion {
+    let drift = |rng: &mut Rng| Motion::Drift {
+        speed: random_sign(rng) * random_between(rng, config::DRIFT_MIN_SPEED, config::DRIFT_MAX_SPEED),
+    };
+    let sine = |rng: &mut Rng| Motion::Sine {
+        amplitude: random_between(rng, config::SINE_MIN_AMPLITUDE, config::SINE_MAX_AMPLITUDE),
+        frequency: config::SINE_FREQUENCY,
+    };
+    let bounce = |rng: &mut Rng| Motion::Bounce {
+        speed: random_sign(rng) * random_between(rng, config::BOUNCE_MIN_SPEED, config::BOUNCE_MAX_SPEED),
+    };
+
+    match kind {
+        CatKind::Golden => return drift(rng),
+        CatKind::Ghost => return sine(rng),
+        CatKind::Heart => return Motion::Parachute,
+        CatKind::Angel if rng.unit() < config::ANGEL_PARACHUTE_CHANCE => {
+            return Motion::Parachute;
+        }
+        _ => {}
+    }
+
+    let variety = ((elapsed_time - config::MOTION_RAMP_START) / config::MOTION_RAMP_DURATION)
+        .clamp(0.0, config::MOTION_MAX_CHANCE);
+    if rng.unit() >= variety {
+        return Motion::Straight;
+    }
+    match rng.range_i32(0..=2) {
+        0 => drift(rng),
+        1 => sine(rng),
+        _ => bounce(rng),
     }
 }
 
@@ -0,0 +1,106 @@
+use crate::game::effects::MusicMode;
+use crate::game::mode::RunEnd;
+use crate::game::world::World;
+use crate::input::Input;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum VersusOutcome {
+    Winner(usize),
+    Draw,
+}
+
+/// Split-screen match: one `World` per player, built from the same setup and seed, with
+/// devils caught on one side landing on the other.
+pub struct Versus {
+    pub worlds: [World; 2],
+    outcome: Option<VersusOutcome>,
+}
+
+impl Versus {
+    pub fn new(left: World, right: World) -> Self {
+        Self {
+            worlds: [left, right],
+            outcome: None,
+        }
+    }
+
+    pub fn update(&mut self, inputs: [Input; 2], dt: f32, arena_w: f32, arena_h: f32) {
+        if self.outcome.is_some() {
+            return;
+        }
+        for (world, input) in self.worlds.iter_mut().zip(inputs) {
+            if !world.game_over_trigger {
+                world.update(&[input], dt, arena_w, arena_h);
+            }
+        }
+
+        let [left, right] = &mut self.worlds;
+        for effect in left.take_outgoing() {
+            right.receive_devil_effect(effect);
+        }
+        for effect in right.take_outgoing() {
+            left.receive_devil_effect(effect);
+        }
+
+        self.outcome = self.decide();
+    }
+
+    /// The first giant crying cat to land loses. Runs that end any other way (time up,
+    /// target reached) are settled on score once both are over.
+    fn decide(&self) -> Option<VersusOutcome> {
+        let landed = self
+            .worlds
+            .each_ref()
+            .map(|world| world.game_over_trigger && world.result().end == RunEnd::CatLanded);
+        match landed {
+            [true, false] => return Some(VersusOutcome::Winner(1)),
+            [false, true] => return Some(VersusOutcome::Winner(0)),
+            _ => {}
+        }
+        let reached_target = self
+            .worlds
+            .each_ref()
+            .map(|world| world.game_over_trigger && world.result().end == RunEnd::TargetReached);
+        match reached_target {
+            [true, false] => return Some(VersusOutcome::Winner(0)),
+            [false, true] => return Some(VersusOutcome::Winner(1)),
+            _ => {}
+        }
+        if !self.worlds.iter().all(|world| world.game_over_trigger) {
+            return None;
+        }
+        let (left, right) = (self.worlds[0].score(), self.worlds[1].score());
+        Some(match left.cmp(&right) {
+            std::cmp::Ordering::Greater => VersusOutcome::Winner(0),
+            std::cmp::Ordering::Less => VersusOutcome::Winner(1),
+            std::cmp::Ordering::Equal => VersusOutcome::Draw,
+        })
+    }
+
+    pub fn state_hash(&self) -> u64 {
+        let [left, right] = self.worlds.each_ref().map(World::state_hash);
+        left.rotate_left(1) ^ right
+    }
+
+    pub fn outcome(&self) -> Option<VersusOutcome> {
+        self.outcome
+    }
+
+    pub fn scores(&self) -> [i32; 2] {
+        self.worlds.each_ref().map(|world| world.score())
+    }
+
+    /// Both players hear the left side's soundtrack.
+    pub fn music_mode(&self) -> MusicMode {
+        self.worlds[0].music_mode()
+    }
+
+    /// The angrier side's intensity, so the music heats up for whoever is closer to losing.
+    pub fn music_intensity(&self) -> f32 {
+        self.worlds[0].music_intensity().max(self.worlds[1].music_intensity())
+    }
+
+    pub fn music_pitch(&self) -> f32 {
+        self.worlds[0].music_pitch().max(self.worlds[1].music_pitch())
+    }
+}
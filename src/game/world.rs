@@ -1,74 +1,197 @@
+use std::hash::{DefaultHasher, Hash, Hasher};
+
 use raylib::prelude::*;
 use crate::config;
 use crate::input::Input;
 
+use crate::game::anger::AngerStage;
+use crate::game::animator::{AnimEvent, Animator, Clip, FrameTimes, PlayMode};
+use crate::game::boss::Boss;
 use crate::game::bucket::Bucket;
 use crate::game::collision;
 use crate::game::effects::{DevilEffect, EffectsState, MusicMode};
-use crate::game::objects::FallingObject;
-use crate::game::objects::CatKind;
+use crate::game::events::GameEvent;
+use crate::game::objects::{CatKind, FallingObject, Landing};
+use crate::game::particles::{self, ParticleSystem};
+use crate::game::player::{Player, PlayerStats};
+use crate::game::daily::Modifier;
+use crate::game::level::{self, LevelGoal, LevelScript};
+use crate::game::mode::{GameMode, RunEnd, RunResult, RunSetup};
+use crate::game::rules::RuleSet;
 use crate::game::scoring::Scoring;
-use crate::game::spawn::Spawner;
+use crate::game::spawn::{SpawnSource, Spawner};
+use crate::render::atlas::SpriteId;
+use crate::rng::Rng;
+
+static GIANT_CAT_CRY: Clip = Clip {
+    name: "cry",
+    frames: 4,
+    fps: config::CRYING_CAT_FPS,
+    mode: PlayMode::PingPong,
+    events: &[],
+};
 
 pub struct World {
-    pub bucket: Bucket,
+    pub players: Vec<Player>,
     pub objects: Vec<FallingObject>,
-    spawner: Spawner,
+    // Cats squashing into a bucket; only for show, so kept out of the state hash.
+    caught: Vec<FallingObject>,
+    particles: ParticleSystem,
+    source: SpawnSource,
     scoring: Scoring,
+    // What falls comes from its own stream, so catches cannot change it.
+    spawn_rng: Rng,
+    rng: Rng,
     effects: EffectsState,
     elapsed_time: f32,
     base_bucket_size: Vector2,
-    bucket_scale: f32,
-    
+    modifier_bucket_scale: f32,
+    rules: RuleSet,
+    mode: GameMode,
+    goal: Option<LevelGoal>,
+    devil_effects: Vec<DevilEffect>,
+    music_tracks: usize,
+    end: Option<RunEnd>,
+    arena_w: f32,
+    events: Vec<GameEvent>,
+
+    // Split-screen versus: devils caught here are sent to the rival
+    versus: bool,
+    outgoing: Vec<DevilEffect>,
+
     // Angry Cat Mechanism
     pub angry_points: i32,
     pub max_angry_points: i32,
+    pub lives: i32,
+    pub max_lives: i32,
     pub giant_cat_y: Option<f32>,
-    pub giant_cat_frame: usize,
-    giant_cat_anim_timer: f32,
+    giant_cat_anim: Animator,
+    giant_cat_size: Vector2,
+    frame_times: FrameTimes,
     giant_cat_landed_timer: f32,
     pub game_over_trigger: bool,
     
     // Devil Streak
     missed_devil_streak: i32,
+
+    // Devil Queen boss fights
+    boss: Option<Boss>,
+    boss_timer: f32,
+    boss_appearances: i32,
+
+    // Catching streak cools the cat down
+    catch_streak: i32,
+    anger_decay_timer: f32,
+
+    setup: RunSetup,
 }
 
 impl World {
-    pub fn new(screen_w: f32, screen_h: f32, initial_max_angry: i32) -> Self {
-        let bucket = Bucket::new(screen_w, screen_h);
-        let base_bucket_size = bucket.size;
+    pub fn new(screen_w: f32, screen_h: f32, setup: RunSetup) -> Self {
+        let mut players: Vec<Player> = (0..setup.players.clamp(1, config::MAX_PLAYERS))
+            .map(|_| Player::new(screen_w, screen_h))
+            .collect();
+        let base_bucket_size = players[0].bucket.size;
+        let mut seeder = Rng::new(setup.seed);
+        let spawn_rng = Rng::new(seeder.next_u64());
+        let mut rng = Rng::new(seeder.next_u64());
+        let particles = ParticleSystem::new(seeder.next_u64());
+        let initial_max_angry =
+            rng.range_i32(config::ANGRY_BAR_MIN_MAX..=config::ANGRY_BAR_MAX_MAX);
+
+        let mut effects = EffectsState::new();
+        if setup.modifiers.contains(&Modifier::InvertedStart) {
+            for player in &mut players {
+                player.effects.apply_invert_for(config::DAILY_INVERTED_START_DURATION);
+            }
+        }
+        if let Some(intro) = &setup.intro {
+            effects.set_message(intro);
+        }
+        let modifier_bucket_scale = if setup.modifiers.contains(&Modifier::TinyBucket) {
+            config::DAILY_TINY_BUCKET_SCALE
+        } else {
+            1.0
+        };
+
+        let (source, goal, mut devil_effects) = match &setup.level {
+            Some(level) => (
+                SpawnSource::Script(LevelScript::new(level)),
+                Some(level.goal),
+                level.devil_effects.clone(),
+            ),
+            None => (
+                SpawnSource::Random(Spawner::new(setup.rules, &setup.modifiers)),
+                None,
+                DevilEffect::ALL.to_vec(),
+            ),
+        };
+        if setup.versus {
+            // Both sides share one soundtrack, and a devil sent to the rival must not
+            // hand them a score multiplier.
+            devil_effects.retain(|effect| {
+                !matches!(
+                    effect,
+                    DevilEffect::MusicSwap | DevilEffect::ScoreDouble | DevilEffect::ScoreTriple
+                )
+            });
+        }
+
         Self {
-            bucket,
+            players,
             objects: Vec::new(),
-            spawner: Spawner::new(),
+            caught: Vec::new(),
+            particles,
+            source,
             scoring: Scoring::new(),
-            effects: EffectsState::new(),
+            spawn_rng,
+            rng,
+            effects,
             elapsed_time: 0.0,
             base_bucket_size,
-            bucket_scale: 1.0,
+            modifier_bucket_scale,
+            rules: setup.rules,
+            mode: setup.mode,
+            goal,
+            devil_effects,
+            music_tracks: 0,
+            end: None,
+            arena_w: screen_w,
+            events: Vec::new(),
+            versus: setup.versus,
+            outgoing: Vec::new(),
 
             angry_points: 0,
             max_angry_points: initial_max_angry,
+            lives: config::CLASSIC_LIVES,
+            max_lives: config::CLASSIC_LIVES,
             giant_cat_y: None,
-            giant_cat_frame: 0,
             giant_cat_landed_timer: 0.0,
-            giant_cat_anim_timer: 0.0,
+            giant_cat_anim: Animator::new(SpriteId::CryingCat, &GIANT_CAT_CRY),
+            giant_cat_size: Vector2::new(1.0, 1.0),
+            frame_times: FrameTimes::default(),
             game_over_trigger: false,
             missed_devil_streak: 0,
+            boss: None,
+            boss_timer: 0.0,
+            boss_appearances: 0,
+            catch_streak: 0,
+            anger_decay_timer: 0.0,
+            setup,
         }
     }
 
-    pub fn update(
-        &mut self,
-        rl: &RaylibHandle,
-        input: Input,
-        dt: f32,
-        screen_w: f32,
-        screen_h: f32,
-    ) {
+    /// `inputs` holds one entry per player; missing entries leave that bucket idle.
+    pub fn update(&mut self, inputs: &[Input], dt: f32, screen_w: f32, screen_h: f32) {
+        self.arena_w = screen_w;
+        let times = &self.frame_times;
+        self.caught
+            .retain_mut(|obj| !obj.animate(dt, times).contains(&AnimEvent::Vanish));
+        self.particles.update(dt);
         if let Some(y) = self.giant_cat_y {
+            self.cry(y, dt);
             let speed = config::GIANT_CAT_SPEED;
-            let target_y = screen_h - (config::CRYING_CAT_FRAME_H * config::CRYING_CAT_SCALE);
+            let target_y = screen_h - (self.giant_cat_size.y * self.giant_cat_scale());
             
             // Move if we haven't reached the target
             if y < target_y {
@@ -78,18 +201,17 @@ impl World {
                 self.giant_cat_y = Some(final_y);
                 
                 // Animate Giant Cat while moving
-                self.giant_cat_anim_timer += dt;
-                if self.giant_cat_anim_timer >= 1.0 / config::CRYING_CAT_FPS {
-                    self.giant_cat_anim_timer = 0.0;
-                    self.giant_cat_frame = (self.giant_cat_frame + 1) % config::CRYING_CAT_FRAMES;
-                }
+                self.giant_cat_anim.advance(dt);
                 
                 if final_y >= target_y {
                     // self.game_over_trigger = true;
+                    if self.giant_cat_landed_timer == 0.0 {
+                        self.events.push(GameEvent::GiantCatLanded);
+                    }
                     // Start timer
                     self.giant_cat_landed_timer += dt;
                     if self.giant_cat_landed_timer >= config::GIANT_CAT_LANDED_DELAY {
-                        self.game_over_trigger = true;
+                        self.finish(RunEnd::CatLanded);
                     }
                 }
             } else {
@@ -97,80 +219,468 @@ impl World {
                 self.giant_cat_y = Some(target_y);
                 self.giant_cat_landed_timer += dt;
                 if self.giant_cat_landed_timer >= config::GIANT_CAT_LANDED_DELAY {
-                    self.game_over_trigger = true;
+                    self.finish(RunEnd::CatLanded);
                 }
             }
             return;
         }
 
         self.elapsed_time += dt;
+        if let GameMode::TimeAttack { seconds } = self.mode
+            && self.elapsed_time >= seconds as f32
+        {
+            self.elapsed_time = seconds as f32;
+            self.finish(RunEnd::TimeUp);
+            return;
+        }
         self.effects.update(dt);
-        let move_x = self.effects.apply_input(input.move_x);
-        let difficulty = 1.0 + self.elapsed_time * config::OBJ_SPEED_SCALE;
-        self.bucket.update(move_x, dt, screen_w, difficulty);
+        let difficulty = self.difficulty();
+        for (i, player) in self.players.iter_mut().enumerate() {
+            let input = inputs.get(i).copied().unwrap_or_default();
+            player.effects.update(dt);
+            let move_x = player.effects.apply_input(input.move_x);
+            let times = &self.frame_times;
+            player.bucket.update(move_x, dt, screen_w, difficulty, times);
+            player.update_size(self.base_bucket_size, self.modifier_bucket_scale, screen_w, screen_h);
+        }
+        self.separate_buckets(screen_w);
 
-        let desired_scale = self.effects.bucket_scale();
-        if (desired_scale - self.bucket_scale).abs() > f32::EPSILON {
-            self.bucket_scale = desired_scale;
-            let size = Vector2::new(
-                self.base_bucket_size.x * desired_scale,
-                self.base_bucket_size.y * desired_scale,
-            );
-            self.bucket.apply_size(size, screen_w, screen_h);
+        self.update_anger_decay(dt);
+        let furious = self.anger_stage() == AngerStage::Furious;
+        for player in &mut self.players {
+            player.bucket.set_panicking(furious);
         }
 
-        if let Some(obj) = self.spawner.update(rl, dt, screen_w, self.elapsed_time) {
-            self.objects.push(obj);
+        let stage = self.anger_stage();
+        let boss_fight = self.update_boss(dt, screen_w);
+        match &mut self.source {
+            SpawnSource::Random(_) if boss_fight => {}
+            SpawnSource::Random(spawner) => {
+                if let Some(obj) = spawner.update(&mut self.spawn_rng, dt, screen_w, self.elapsed_time, stage) {
+                    self.objects.push(obj);
+                }
+            }
+            SpawnSource::Script(script) => self.objects.extend(script.update(dt, screen_w)),
         }
 
         for obj in &mut self.objects {
-            obj.update(dt);
+            obj.update(dt, screen_w);
+            if matches!(obj.kind(), CatKind::Devil | CatKind::Explode) {
+                self.particles.stream(&particles::DEVIL_SMOKE, obj.pos, dt);
+            }
         }
 
+        let mut split = Vec::new();
+        self.objects.retain(|obj| {
+            if obj.should_split(screen_h) {
+                split.extend(obj.split());
+                false
+            } else {
+                true
+            }
+        });
+        self.objects.extend(split);
+
         let mut remaining = Vec::with_capacity(self.objects.len());
         let mut caught = Vec::new();
-        let bucket_rect = self.bucket.rect();
-        for obj in self.objects.drain(..) {
-            if collision::check_collision(obj.pos, obj.radius, bucket_rect) {
-                caught.push(obj.kind());
+        let mut missed = Vec::new();
+        let bucket_rects: Vec<(Rectangle, Rectangle)> = self
+            .players
+            .iter()
+            .map(|p| (p.bucket.prev_rect(), p.bucket.rect()))
+            .collect();
+        for mut obj in self.objects.drain(..) {
+            let catcher = bucket_rects.iter().position(|(before, after)| {
+                collision::check_swept_collision(obj.prev_pos(), obj.pos, obj.radius, *before, *after)
+            });
+            if let Some(player) = catcher {
+                caught.push((obj.kind(), player, obj.pos));
+                if obj.kind() != CatKind::Heart {
+                    obj.catch();
+                    self.caught.push(obj);
+                }
                 continue;
             }
             if !obj.offscreen(screen_h) {
                 remaining.push(obj);
             } else {
-                 match obj.kind() {
-                     CatKind::Normal => self.angry_points += config::ANGRY_PENALTY_NORMAL,
-                     CatKind::Angel => self.angry_points += config::ANGRY_PENALTY_ANGEL,
-                     CatKind::Devil => {
-                         // Consecutive devil miss logic
-                         self.missed_devil_streak += 1;
-                         if self.missed_devil_streak > 0 && self.missed_devil_streak % config::DEVIL_MISS_STREAK_LIMIT == 0 {
-                             self.angry_points += config::DEVIL_MISS_PENALTY;
-                         }
-                     },
-                     _ => {}
-                 }
+                missed.push(obj.kind());
             }
         }
         self.objects = remaining;
-        for kind in caught {
+        for kind in missed {
+            self.handle_miss(kind);
+        }
+        for (kind, player, pos) in caught {
              // Reset streak specifically when a Devil cat is caught
              if let CatKind::Devil = kind {
                  self.missed_devil_streak = 0;
              }
-            self.handle_catch(kind, rl);
+            self.handle_catch(kind, player, pos);
+        }
+
+        if let GameMode::Target { points } = self.mode
+            && self.score() >= points
+        {
+            self.finish(RunEnd::TargetReached);
+            return;
+        }
+
+        if let Some(end) = self.level_end() {
+            self.finish(end);
+            return;
         }
 
-        if self.angry_points >= self.max_angry_points && self.giant_cat_y.is_none() {
+        if self.run_lost() {
+            self.start_giant_cat();
+        }
+    }
+
+    /// Buckets cannot pass through each other: overlapping ones are pushed apart and
+    /// trade their sideways speed.
+    fn separate_buckets(&mut self, screen_w: f32) {
+        for i in 0..self.players.len() {
+            for j in i + 1..self.players.len() {
+                let (head, tail) = self.players.split_at_mut(j);
+                let a = &mut head[i].bucket;
+                let b = &mut tail[0].bucket;
+                let (left, right) = if a.pos.x <= b.pos.x { (a, b) } else { (b, a) };
+                let overlap = left.pos.x + left.size.x - right.pos.x;
+                if overlap <= 0.0 {
+                    continue;
+                }
+                left.shift(-overlap / 2.0, screen_w);
+                right.shift(overlap / 2.0, screen_w);
+                // One of them may be pinned against a wall; the other takes the rest.
+                let remaining = left.pos.x + left.size.x - right.pos.x;
+                if remaining > 0.0 {
+                    if left.pos.x <= 0.0 {
+                        right.shift(remaining, screen_w);
+                    } else {
+                        left.shift(-remaining, screen_w);
+                    }
+                }
+                let (left_vel, right_vel) = (left.velocity_x(), right.velocity_x());
+                left.set_velocity_x(right_vel.min(0.0));
+                right.set_velocity_x(left_vel.max(0.0));
+            }
+        }
+    }
+
+    /// Runs the current boss fight, or counts down to the next one in endless-style runs.
+    /// Returns `true` while regular spawning should hold off.
+    fn update_boss(&mut self, dt: f32, screen_w: f32) -> bool {
+        if let Some(boss) = &mut self.boss {
+            self.objects.extend(boss.update(dt, screen_w, &mut self.spawn_rng));
+            let fighting = boss.fighting();
+            if boss.finished() {
+                self.boss = None;
+            }
+            return fighting;
+        }
+        if !matches!(self.source, SpawnSource::Random(_)) {
+            return false;
+        }
+        self.boss_timer += dt;
+        if self.boss_timer < config::BOSS_INTERVAL {
+            return false;
+        }
+        self.boss_timer = 0.0;
+        self.boss = Some(Boss::new(screen_w, self.boss_appearances));
+        self.boss_appearances += 1;
+        self.effects.set_message("The Devil Queen appears! Catch angels to hurt her!");
+        true
+    }
+
+    /// Angels are holy to the Devil Queen; each one caught during her fight hurts her.
+    fn hit_boss(&mut self) {
+        if let Some(boss) = &mut self.boss
+            && boss.hit(config::BOSS_HOLY_DAMAGE)
+        {
+            self.defeat_boss();
+        }
+    }
+
+    fn defeat_boss(&mut self) {
+        self.scoring.add_bonus(config::BOSS_SCORE);
+        self.angry_points = (self.angry_points - config::BOSS_CALM_POINTS).max(0);
+        self.lives = (self.lives + 1).min(self.max_lives);
+        self.effects
+            .set_message(&format!("Devil Queen defeated! +{}", config::BOSS_SCORE));
+    }
+
+    pub fn boss(&self) -> Option<&Boss> {
+        self.boss.as_ref()
+    }
+
+    fn level_end(&self) -> Option<RunEnd> {
+        let script_done = match &self.source {
+            SpawnSource::Script(script) => script.finished() && self.objects.is_empty(),
+            SpawnSource::Random(_) => false,
+        };
+        match self.goal? {
+            LevelGoal::Survive { seconds } if self.elapsed_time >= seconds => {
+                Some(RunEnd::LevelCleared)
+            }
+            LevelGoal::Score { points } if self.score() >= points => Some(RunEnd::LevelCleared),
+            LevelGoal::Score { .. } if script_done => Some(RunEnd::OutOfCats),
+            _ => None,
+        }
+    }
+
+    fn start_giant_cat(&mut self) {
+        if self.giant_cat_y.is_none() {
              self.giant_cat_y = Some(-800.0); // Start way above
         }
     }
 
+    /// Tears from both of the giant cat's eyes; `y` is the top of the sprite.
+    fn cry(&mut self, y: f32, dt: f32) {
+        let size = self.giant_cat_size * self.giant_cat_scale();
+        let eye_y = y + size.y * config::GIANT_CAT_EYE_Y;
+        for side in [-1.0, 1.0] {
+            let eye_x = self.arena_w / 2.0 + side * size.x * config::GIANT_CAT_EYE_X;
+            self.particles
+                .stream(&particles::GIANT_CAT_TEARS, Vector2::new(eye_x, eye_y), dt);
+        }
+    }
+
+    fn finish(&mut self, end: RunEnd) {
+        self.end = Some(end);
+        self.game_over_trigger = true;
+    }
+
+    pub fn mode(&self) -> GameMode {
+        self.mode
+    }
+
+    pub fn level_goal(&self) -> Option<LevelGoal> {
+        self.goal
+    }
+
+    pub fn elapsed_time(&self) -> f32 {
+        self.elapsed_time
+    }
+
+    /// How much faster buckets and new cats are than at the start of the run.
+    pub fn difficulty(&self) -> f32 {
+        1.0 + self.elapsed_time * config::OBJ_SPEED_SCALE
+    }
+
+    /// What this world was started with, for restarting it.
+    pub fn setup(&self) -> &RunSetup {
+        &self.setup
+    }
+
+    /// The random spawner's odds right now; `None` for scripted levels.
+    pub fn spawn_rates(&self) -> Option<[(CatKind, f32); 7]> {
+        match &self.source {
+            SpawnSource::Random(spawner) => Some(spawner.rates(self.anger_stage())),
+            SpawnSource::Script(_) => None,
+        }
+    }
+
+    /// Running effect timers, with the player they belong to when there is more than one.
+    pub fn effect_timers(&self) -> Vec<(String, f32)> {
+        let mut timers: Vec<(String, f32)> = self
+            .effects
+            .timers()
+            .into_iter()
+            .map(|(name, time)| (name.to_string(), time))
+            .collect();
+        for (i, player) in self.players.iter().enumerate() {
+            for (name, time) in player.effects.timers() {
+                let label = if self.players.len() > 1 {
+                    format!("P{} {}", i + 1, name)
+                } else {
+                    name.to_string()
+                };
+                timers.push((label, time));
+            }
+        }
+        timers
+    }
+
+    /// Fingerprint of the simulation state; lockstep peers compare these to catch desyncs.
+    pub fn state_hash(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        self.score().hash(&mut hasher);
+        self.angry_points.hash(&mut hasher);
+        self.lives.hash(&mut hasher);
+        self.elapsed_time.to_bits().hash(&mut hasher);
+        for player in &self.players {
+            player.bucket.pos.x.to_bits().hash(&mut hasher);
+            player.bucket.size.x.to_bits().hash(&mut hasher);
+        }
+        for obj in &self.objects {
+            obj.pos.x.to_bits().hash(&mut hasher);
+            obj.pos.y.to_bits().hash(&mut hasher);
+        }
+        self.giant_cat_y.map(f32::to_bits).hash(&mut hasher);
+        hasher.finish()
+    }
+
+    /// Width of the play field this world runs in; half the window in split-screen.
+    pub fn arena_width(&self) -> f32 {
+        self.arena_w
+    }
+
+    /// Size of one frame of the giant cat's sprite, before scaling.
+    pub fn set_giant_cat_size(&mut self, size: Vector2) {
+        self.giant_cat_size = size;
+    }
+
+    /// The sprite sheets' frame times, which the animators here play by.
+    pub fn set_frame_times(&mut self, times: FrameTimes) {
+        self.frame_times = times;
+    }
+
+    pub fn giant_cat_animator(&self) -> &Animator {
+        &self.giant_cat_anim
+    }
+
+    pub fn caught_cats(&self) -> &[FallingObject] {
+        &self.caught
+    }
+
+    /// The giant crying cat fills the arena, shrinking to fit a narrow one.
+    pub fn giant_cat_scale(&self) -> f32 {
+        config::CRYING_CAT_SCALE.min(self.arena_w / self.giant_cat_size.x.max(1.0))
+    }
+
+    pub fn bucket(&self) -> &Bucket {
+        &self.players[0].bucket
+    }
+
+    pub fn player_stats(&self) -> Vec<PlayerStats> {
+        self.players.iter().map(|p| p.stats).collect()
+    }
+
+    pub fn result(&self) -> RunResult {
+        let end = self.end.unwrap_or(RunEnd::Quit);
+        let stars = self.goal.map(|_| {
+            if end == RunEnd::LevelCleared {
+                level::stars_for(self.anger_fraction())
+            } else {
+                0
+            }
+        });
+        RunResult {
+            mode: self.mode,
+            score: self.score(),
+            elapsed: self.elapsed_time,
+            end,
+            stars,
+            players: self.player_stats(),
+        }
+    }
+
+    fn handle_miss(&mut self, kind: CatKind) {
+        self.events.push(GameEvent::Missed(kind));
+        match kind {
+            CatKind::Normal | CatKind::Ghost | CatKind::Splitting => {
+                self.penalize(config::ANGRY_PENALTY_NORMAL);
+                if self.mode == GameMode::SuddenDeath {
+                    self.start_giant_cat();
+                }
+            }
+            CatKind::Angel => self.penalize(config::ANGRY_PENALTY_ANGEL),
+            CatKind::Devil => {
+                // Consecutive devil miss logic
+                self.missed_devil_streak += 1;
+                if self.missed_devil_streak > 0
+                    && self.missed_devil_streak % config::DEVIL_MISS_STREAK_LIMIT == 0
+                {
+                    self.add_anger(config::DEVIL_MISS_PENALTY);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn update_anger_decay(&mut self, dt: f32) {
+        if self.catch_streak < config::ANGRY_DECAY_STREAK || self.angry_points <= 0 {
+            self.anger_decay_timer = 0.0;
+            return;
+        }
+        self.anger_decay_timer += dt;
+        if self.anger_decay_timer >= config::ANGRY_DECAY_INTERVAL {
+            self.anger_decay_timer = 0.0;
+            self.angry_points -= 1;
+        }
+    }
+
+    /// A mistake costs anger under the angry bar rules and one heart under classic lives.
+    fn penalize(&mut self, anger: i32) {
+        self.catch_streak = 0;
+        match self.rules {
+            RuleSet::AngryBar => self.angry_points += anger,
+            RuleSet::ClassicLives => self.lives = (self.lives - 1).max(0),
+        }
+    }
+
+    fn add_anger(&mut self, anger: i32) {
+        if self.rules == RuleSet::AngryBar {
+            self.angry_points += anger;
+        }
+    }
+
+    fn run_lost(&self) -> bool {
+        match self.rules {
+            RuleSet::AngryBar => self.angry_points >= self.max_angry_points,
+            RuleSet::ClassicLives => self.lives <= 0,
+        }
+    }
+
+    pub fn rules(&self) -> RuleSet {
+        self.rules
+    }
+
+    /// How close the run is to being lost, from 0.0 to 1.0, under either rule set.
+    pub fn anger_fraction(&self) -> f32 {
+        let fraction = match self.rules {
+            RuleSet::AngryBar => self.angry_points as f32 / self.max_angry_points.max(1) as f32,
+            RuleSet::ClassicLives => 1.0 - self.lives as f32 / self.max_lives.max(1) as f32,
+        };
+        fraction.clamp(0.0, 1.0)
+    }
+
+    pub fn anger_stage(&self) -> AngerStage {
+        AngerStage::from_fraction(self.anger_fraction())
+    }
+
     pub fn score(&self) -> i32 {
         self.scoring.score()
     }
 
+    /// How hectic the run is, from 0.0 to 1.0, for the music to follow.
+    pub fn music_intensity(&self) -> f32 {
+        if self.giant_cat_y.is_some() {
+            return 1.0;
+        }
+        let weight = config::MUSIC_INTENSITY_TIME_WEIGHT;
+        let mut intensity = self.run_ramp() * weight + self.anger_fraction() * (1.0 - weight);
+        let devil_active = self.effects.any_active()
+            || self.players.iter().any(|player| player.effects.any_active());
+        if devil_active {
+            intensity += config::MUSIC_INTENSITY_DEVIL_BOOST;
+        }
+        intensity.clamp(0.0, 1.0)
+    }
+
+    /// The anger stage's pitch, nudged up a little more the longer the run lasts.
+    pub fn music_pitch(&self) -> f32 {
+        self.anger_stage().music_pitch() * (1.0 + config::MUSIC_TEMPO_RAMP * self.run_ramp())
+    }
+
+    fn run_ramp(&self) -> f32 {
+        (self.elapsed_time / config::MUSIC_INTENSITY_RAMP_TIME).clamp(0.0, 1.0)
+    }
+
     pub fn music_mode(&self) -> MusicMode {
+        if self.boss.as_ref().is_some_and(|boss| boss.fighting()) {
+            return MusicMode::Boss;
+        }
         self.effects.music_mode()
     }
 
@@ -182,96 +692,198 @@ impl World {
         self.effects.message_alpha()
     }
 
-    pub fn explosion_effect(&self) -> Option<(Vector2, f32)> {
-        self.effects.explosion()
+    pub fn particles(&self) -> &ParticleSystem {
+        &self.particles
     }
 
-    pub fn set_base_bucket_size(&mut self, size: Vector2) {
+    /// Whether player one's controls are flipped, for the autopilot.
+    pub fn controls_inverted(&self) -> bool {
+        self.players[0].effects.controls_inverted()
+    }
+
+    /// Where each falling object will reach the top of the bucket, for the autopilot and
+    /// the landing markers.
+    pub fn landing_predictions(&self, screen_w: f32) -> Vec<(CatKind, Landing)> {
+        let target_y = self.bucket().pos.y;
+        self.objects
+            .iter()
+            .filter_map(|obj| {
+                obj.predict_landing(target_y, screen_w)
+                    .map(|landing| (obj.kind(), landing))
+            })
+            .collect()
+    }
+
+    /// How many devil-swap tracks the audio side loaded. With none, devils never swap music.
+    pub fn set_music_tracks(&mut self, count: usize) {
+        self.music_tracks = count;
+        if count == 0 {
+            self.devil_effects.retain(|effect| *effect != DevilEffect::MusicSwap);
+        }
+    }
+
+    /// Sets every bucket to its sprite size and spreads the players evenly along the floor.
+    pub fn set_bucket_size(&mut self, size: Vector2, screen_w: f32, screen_h: f32) {
         self.base_bucket_size = size;
-        self.bucket_scale = 1.0;
+        let count = self.players.len() as f32;
+        for (i, player) in self.players.iter_mut().enumerate() {
+            player.bucket.set_size(size, screen_w, screen_h);
+            player.bucket.place_at(screen_w * (i as f32 + 1.0) / (count + 1.0), screen_w);
+            player.reset_scale();
+        }
     }
 
-    fn handle_catch(&mut self, kind: CatKind, rl: &RaylibHandle) {
+    /// Messages about one bucket say whose it is when more than one player is in.
+    fn announce(&mut self, player: usize, text: &str) {
+        if self.players.len() > 1 {
+            self.effects.set_message(&format!("P{}: {}", player + 1, text));
+        } else {
+            self.effects.set_message(text);
+        }
+    }
+
+    fn handle_catch(&mut self, kind: CatKind, player: usize, pos: Vector2) {
+        if matches!(
+            kind,
+            CatKind::Normal | CatKind::Angel | CatKind::Golden | CatKind::Ghost | CatKind::Splitting
+        ) {
+            self.catch_streak += 1;
+        }
+        self.events.push(GameEvent::Caught(kind));
+        self.players[player].bucket.react_to_catch();
+        self.particles
+            .burst_tinted(&particles::CATCH_SPARKS, pos, particles::catch_color(kind));
+        if matches!(kind, CatKind::Angel | CatKind::Heart) {
+            self.particles.burst(&particles::ANGEL_HEARTS, pos);
+        }
         let multiplier = self.effects.score_multiplier();
-        self.scoring.register_catch(kind, rl, multiplier);
+        let gained = self.scoring.register_catch(kind, &mut self.rng, multiplier);
+        self.players[player].stats.record(kind, gained);
 
         match kind {
             CatKind::Devil => {
-                let effect = self.random_devil_effect(rl);
-                self.apply_devil_effect(effect, rl);
+                if let Some(effect) = self.random_devil_effect() {
+                    self.send_or_apply(effect, player);
+                }
             }
             CatKind::Explode => {
-                self.apply_devil_effect(DevilEffect::BucketExplode, rl);
+                self.send_or_apply(DevilEffect::BucketExplode, player);
+            }
+            CatKind::Bomb => {
+                self.penalize(config::ANGRY_PENALTY_BOMB);
+                let center = self.players[player].bucket_center();
+                self.particles.explosion(center);
+                self.players[player].bucket.explode();
+                self.events.push(GameEvent::BucketExploded);
+                self.announce(player, "Bomb cat! The cat is furious!");
+            }
+            CatKind::Angel => self.hit_boss(),
+            CatKind::Heart => {
+                self.lives = (self.lives + 1).min(self.max_lives);
+                self.effects.set_message("Extra heart!");
+            }
+            CatKind::Golden => {
+                self.effects.set_message(&format!("Golden cat! +{}", gained));
             }
             _ => {}
         }
     }
 
-    fn random_devil_effect(&self, rl: &RaylibHandle) -> DevilEffect {
-        let explode_roll = rl.get_random_value::<i32>(0..1000) as f32 / 1000.0;
-        if explode_roll < config::EXPLODE_RATE_IN_DEVIL {
-            return DevilEffect::BucketExplode;
+    fn send_or_apply(&mut self, effect: DevilEffect, player: usize) {
+        if self.versus {
+            self.outgoing.push(effect);
+            self.effects.set_message("Devil sent to your rival!");
+        } else {
+            self.apply_devil_effect(effect, player);
+        }
+    }
+
+    /// Events since the last call, oldest first.
+    pub fn take_events(&mut self) -> Vec<GameEvent> {
+        std::mem::take(&mut self.events)
+    }
+
+    /// Devil effects caught since the last call, to be delivered to the rival world.
+    pub fn take_outgoing(&mut self) -> Vec<DevilEffect> {
+        std::mem::take(&mut self.outgoing)
+    }
+
+    /// A devil the rival caught lands on this world's first bucket.
+    pub fn receive_devil_effect(&mut self, effect: DevilEffect) {
+        if self.game_over_trigger || self.giant_cat_y.is_some() {
+            return;
+        }
+        self.apply_devil_effect(effect, 0);
+    }
+
+    /// Picks from the effects this run allows; levels can narrow the list or turn it off.
+    fn random_devil_effect(&mut self) -> Option<DevilEffect> {
+        let explode_allowed = self.devil_effects.contains(&DevilEffect::BucketExplode);
+        if explode_allowed && self.rng.unit() < config::EXPLODE_RATE_IN_DEVIL {
+            return Some(DevilEffect::BucketExplode);
         }
 
-        let effects = [
-            DevilEffect::InvertControls,
-            DevilEffect::BucketSmall,
-            DevilEffect::BucketLarge,
-            DevilEffect::MusicSwap,
-            DevilEffect::ScoreDouble,
-            DevilEffect::ScoreTriple,
-        ];
-        // get_random_value uses an inclusive max; for 6 items, max index is 5.
+        let effects: Vec<DevilEffect> = self
+            .devil_effects
+            .iter()
+            .copied()
+            .filter(|effect| *effect != DevilEffect::BucketExplode)
+            .collect();
+        if effects.is_empty() {
+            return explode_allowed.then_some(DevilEffect::BucketExplode);
+        }
         let max_index = effects.len() as i32 - 1;
-        let index = rl.get_random_value::<i32>(0..max_index) as usize;
-        effects[index]
+        let index = self.rng.range_i32(0..=max_index) as usize;
+        Some(effects[index])
     }
 
-    fn apply_devil_effect(&mut self, effect: DevilEffect, rl: &RaylibHandle) {
+    /// Bucket effects only hit `player`, the one who caught the devil.
+    fn apply_devil_effect(&mut self, effect: DevilEffect, player: usize) {
+        self.events.push(GameEvent::DevilEffect(effect));
         match effect {
             DevilEffect::InvertControls => {
-                self.effects.apply_invert();
-                self.effects.set_message("Controls inverted!");
+                self.players[player].effects.apply_invert();
+                self.announce(player, "Controls inverted!");
             }
             DevilEffect::BucketSmall => {
-                self.effects.apply_size_scale(config::DEVIL_SIZE_SMALL_SCALE);
-                self.effects.set_message("Bucket shrunk!");
+                self.players[player]
+                    .effects
+                    .apply_size_scale(config::DEVIL_SIZE_SMALL_SCALE);
+                self.announce(player, "Bucket shrunk!");
             }
             DevilEffect::BucketLarge => {
-                self.effects.apply_size_scale(config::DEVIL_SIZE_LARGE_SCALE);
-                self.effects.set_message("Bucket enlarged!");
+                self.players[player]
+                    .effects
+                    .apply_size_scale(config::DEVIL_SIZE_LARGE_SCALE);
+                self.announce(player, "Bucket enlarged!");
             }
             DevilEffect::MusicSwap => {
-                // 0..5: Alternate tracks (6 tracks total index 0-5)
-                // 6: Default track
-                // GetRandomValue(0, 6) returns 0..6 inclusive
-                let mut pick = rl.get_random_value::<i32>(0..6);
-                let current_mode = self.effects.music_mode();
-
-                loop {
-                    let is_same = match current_mode {
-                        MusicMode::Default => pick == 6,
-                        MusicMode::Alternate(idx) => pick == idx as i32,
-                    };
-
-                    if !is_same { break; }
-                    pick = rl.get_random_value::<i32>(0..6);
-                }
-
-                if pick == 6 {
-                    self.effects.set_music_default();
-                    self.effects.set_message("Music Reset!");
-                } else {
-                    self.effects.apply_music_swap(pick as usize);
-                    self.effects.set_message("Music changed!");
+                // Any loaded swap track or the default, never the one already playing.
+                // Always one draw, so the outcome stream does not depend on the track count.
+                let current = self.effects.music_mode();
+                let choices: Vec<MusicMode> = std::iter::once(MusicMode::Default)
+                    .chain((0..self.music_tracks).map(MusicMode::Alternate))
+                    .filter(|mode| *mode != current)
+                    .collect();
+                let pick = (self.rng.next_u64() % choices.len().max(1) as u64) as usize;
+                match choices.get(pick) {
+                    Some(MusicMode::Alternate(idx)) => {
+                        self.effects.apply_music_swap(*idx);
+                        self.effects.set_message("Music changed!");
+                    }
+                    _ => {
+                        self.effects.set_music_default();
+                        self.effects.set_message("Music Reset!");
+                    }
                 }
             }
             DevilEffect::BucketExplode => {
-                let rect = self.bucket.rect();
-                let center = Vector2::new(rect.x + rect.width / 2.0, rect.y + rect.height / 2.0);
-                self.effects.trigger_explosion(center);
+                let center = self.players[player].bucket_center();
+                self.particles.explosion(center);
+                self.players[player].bucket.explode();
+                self.events.push(GameEvent::BucketExploded);
                 self.scoring.reset();
-                self.effects.set_message("Bucket exploded! Score reset!");
+                self.announce(player, "Bucket exploded! Score reset!");
             }
             DevilEffect::ScoreDouble => {
                 self.scoring.apply_multiplier(2);
@@ -1,5 +1,6 @@
 use raylib::prelude::*;
 use crate::config;
+use crate::settings::ControlScheme;
 
 #[derive(Default, Clone, Copy)]
 pub struct Input {
@@ -16,29 +17,53 @@ fn first_gamepad_id(rl: &RaylibHandle) -> Option<i32> {
     None
 }
 
+fn keyboard_axis(left: bool, right: bool) -> f32 {
+    match (left, right) {
+        (true, false) => -config::KEYBOARD_AXIS,
+        (false, true) => config::KEYBOARD_AXIS,
+        _ => 0.0,
+    }
+}
+
+fn gamepad_axis(rl: &RaylibHandle, id: i32) -> f32 {
+    if !rl.is_gamepad_available(id) {
+        return 0.0;
+    }
+    let axis = rl.get_gamepad_axis_movement(id, GamepadAxis::GAMEPAD_AXIS_LEFT_X);
+    if axis.abs() < config::GAMEPAD_DEADZONE { 0.0 } else { axis }
+}
+
 impl Input {
-    pub fn gather(rl: &RaylibHandle) -> Self {
+    pub fn gather(rl: &RaylibHandle, controls: ControlScheme) -> Self {
         // Keyboard axis
-        let left = rl.is_key_down(KeyboardKey::KEY_LEFT) || rl.is_key_down(KeyboardKey::KEY_A);
-        let right = rl.is_key_down(KeyboardKey::KEY_RIGHT) || rl.is_key_down(KeyboardKey::KEY_D);
-
-        let axis_kb = match (left, right) {
-            (true, false) => -config::KEYBOARD_AXIS,
-            (false, true) => config::KEYBOARD_AXIS,
-            _ => 0.0,
-        };
+        let wasd = controls != ControlScheme::Arrows;
+        let arrows = controls != ControlScheme::Wasd;
+        let left = (arrows && rl.is_key_down(KeyboardKey::KEY_LEFT))
+            || (wasd && rl.is_key_down(KeyboardKey::KEY_A));
+        let right = (arrows && rl.is_key_down(KeyboardKey::KEY_RIGHT))
+            || (wasd && rl.is_key_down(KeyboardKey::KEY_D));
+        let axis_kb = keyboard_axis(left, right);
 
         // Gamepad axis (left stick X)
-        let mut axis_gp = 0.0;
-        if let Some(id) = first_gamepad_id(rl) {
-            axis_gp = rl.get_gamepad_axis_movement(id, GamepadAxis::GAMEPAD_AXIS_LEFT_X);
-
-            // Deadzone
-            if axis_gp.abs() < config::GAMEPAD_DEADZONE {
-                axis_gp = 0.0;
-            }
-        }
+        let axis_gp = first_gamepad_id(rl).map_or(0.0, |id| gamepad_axis(rl, id));
+
+        Self::combine(axis_kb, axis_gp)
+    }
+
+    /// Co-op splits the keyboard: player one steers with A/D and player two with the
+    /// arrow keys. Each player also gets the gamepad with their own index.
+    pub fn gather_player(rl: &RaylibHandle, player: usize) -> Self {
+        let (left_key, right_key) = if player == 0 {
+            (KeyboardKey::KEY_A, KeyboardKey::KEY_D)
+        } else {
+            (KeyboardKey::KEY_LEFT, KeyboardKey::KEY_RIGHT)
+        };
+        let axis_kb = keyboard_axis(rl.is_key_down(left_key), rl.is_key_down(right_key));
+        let axis_gp = gamepad_axis(rl, player as i32);
+        Self::combine(axis_kb, axis_gp)
+    }
 
+    fn combine(axis_kb: f32, axis_gp: f32) -> Self {
         // Prefer gamepad if active, otherwise keyboard
         let (move_x, using_gamepad) = if axis_gp.abs() > axis_kb.abs() {
             (axis_gp, axis_gp.abs() > 0.0)
@@ -1,7 +1,13 @@
 mod app;
+mod assets;
+mod audio;
 mod config;
+mod debug;
 mod input;
+mod net;
+mod profile;
 mod rng;
+mod settings;
 mod ui;
 
 mod render;
@@ -0,0 +1,212 @@
+use raylib::prelude::*;
+use crate::game::mode::GameMode;
+use crate::game::rules::RuleSet;
+use crate::input::Input;
+
+const MAGIC: &[u8; 4] = b"BCAT";
+pub const PROTOCOL_VERSION: u8 = 4;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum MatchKind {
+    Versus,
+    CoOp,
+}
+
+impl MatchKind {
+    pub fn label(self) -> &'static str {
+        match self {
+            MatchKind::Versus => "Versus",
+            MatchKind::CoOp => "Co-op",
+        }
+    }
+
+    pub fn next(self) -> Self {
+        match self {
+            MatchKind::Versus => MatchKind::CoOp,
+            MatchKind::CoOp => MatchKind::Versus,
+        }
+    }
+}
+
+/// Everything both peers need to build the same match; the host picks it.
+#[derive(Clone, Copy)]
+pub struct MatchInfo {
+    pub seed: u64,
+    pub kind: MatchKind,
+    pub rules: RuleSet,
+    pub mode: GameMode,
+    /// The host's devil-swap track count. Both worlds roll music swaps from it, so a peer
+    /// missing some tracks still draws the same effects.
+    pub music_tracks: u8,
+    /// The host's bucket hitbox and giant cat sizes, from its sprite sheets. Both peers
+    /// simulate with these whatever their own sheets say.
+    pub bucket_size: Vector2,
+    pub giant_cat_size: Vector2,
+}
+
+pub enum Packet {
+    Hello { version: u8 },
+    Welcome { info: MatchInfo },
+    /// Local moves from `first_tick` on, plus how many of the receiver's ticks arrived.
+    /// `hash` repeats the sender's latest state hash and its tick on every packet, so
+    /// the desync check survives lost packets.
+    Inputs {
+        ack: u32,
+        first_tick: u32,
+        moves: Vec<i8>,
+        hash: Option<(u32, u64)>,
+    },
+    Bye,
+}
+
+/// Moves cross the wire as one signed byte. Both peers simulate with the rounded value,
+/// local player included, so they stay bit-identical.
+pub fn quantize(input: Input) -> i8 {
+    (input.move_x.clamp(-1.0, 1.0) * 127.0).round() as i8
+}
+
+pub fn dequantize(value: i8) -> Input {
+    Input {
+        move_x: value as f32 / 127.0,
+        using_gamepad: false,
+    }
+}
+
+impl Packet {
+    pub fn encode(&self) -> Vec<u8> {
+        let mut out = MAGIC.to_vec();
+        match self {
+            Packet::Hello { version } => {
+                out.push(0);
+                out.push(*version);
+            }
+            Packet::Welcome { info } => {
+                out.push(1);
+                out.extend_from_slice(&info.seed.to_le_bytes());
+                out.push(match info.kind {
+                    MatchKind::Versus => 0,
+                    MatchKind::CoOp => 1,
+                });
+                out.push(match info.rules {
+                    RuleSet::AngryBar => 0,
+                    RuleSet::ClassicLives => 1,
+                });
+                let mode = GameMode::ALL.iter().position(|m| *m == info.mode).unwrap_or(0);
+                out.push(mode as u8);
+                out.push(info.music_tracks);
+                for size in [info.bucket_size, info.giant_cat_size] {
+                    out.extend_from_slice(&size.x.to_bits().to_le_bytes());
+                    out.extend_from_slice(&size.y.to_bits().to_le_bytes());
+                }
+            }
+            Packet::Inputs {
+                ack,
+                first_tick,
+                moves,
+                hash,
+            } => {
+                out.push(2);
+                out.extend_from_slice(&ack.to_le_bytes());
+                out.extend_from_slice(&first_tick.to_le_bytes());
+                out.push(moves.len() as u8);
+                out.extend(moves.iter().map(|m| *m as u8));
+                if let Some((tick, hash)) = hash {
+                    out.extend_from_slice(&tick.to_le_bytes());
+                    out.extend_from_slice(&hash.to_le_bytes());
+                }
+            }
+            Packet::Bye => out.push(4),
+        }
+        out
+    }
+
+    /// `None` for anything that is not a well-formed packet of ours.
+    pub fn decode(bytes: &[u8]) -> Option<Packet> {
+        let mut reader = Reader { bytes: bytes.strip_prefix(MAGIC)? };
+        let packet = match reader.u8()? {
+            0 => Packet::Hello { version: reader.u8()? },
+            1 => {
+                let seed = reader.u64()?;
+                let kind = match reader.u8()? {
+                    0 => MatchKind::Versus,
+                    1 => MatchKind::CoOp,
+                    _ => return None,
+                };
+                let rules = match reader.u8()? {
+                    0 => RuleSet::AngryBar,
+                    1 => RuleSet::ClassicLives,
+                    _ => return None,
+                };
+                let mode = *GameMode::ALL.get(reader.u8()? as usize)?;
+                let music_tracks = reader.u8()?;
+                let bucket_size = reader.size()?;
+                let giant_cat_size = reader.size()?;
+                Packet::Welcome {
+                    info: MatchInfo {
+                        seed,
+                        kind,
+                        rules,
+                        mode,
+                        music_tracks,
+                        bucket_size,
+                        giant_cat_size,
+                    },
+                }
+            }
+            2 => {
+                let ack = reader.u32()?;
+                let first_tick = reader.u32()?;
+                let count = reader.u8()? as usize;
+                let moves = reader.take(count)?.iter().map(|b| *b as i8).collect();
+                // The hash is left off until the sender has one.
+                let hash = match reader.bytes.len() {
+                    0 => None,
+                    _ => Some((reader.u32()?, reader.u64()?)),
+                };
+                Packet::Inputs {
+                    ack,
+                    first_tick,
+                    moves,
+                    hash,
+                }
+            }
+            4 => Packet::Bye,
+            _ => return None,
+        };
+        Some(packet)
+    }
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, count: usize) -> Option<&'a [u8]> {
+        if self.bytes.len() < count {
+            return None;
+        }
+        let (head, tail) = self.bytes.split_at(count);
+        self.bytes = tail;
+        Some(head)
+    }
+
+    fn u8(&mut self) -> Option<u8> {
+        Some(self.take(1)?[0])
+    }
+
+    fn u32(&mut self) -> Option<u32> {
+        Some(u32::from_le_bytes(self.take(4)?.try_into().ok()?))
+    }
+
+    fn u64(&mut self) -> Option<u64> {
+        Some(u64::from_le_bytes(self.take(8)?.try_into().ok()?))
+    }
+
+    /// A width and height, which must both be positive.
+    fn size(&mut self) -> Option<Vector2> {
+        let x = f32::from_bits(self.u32()?);
+        let y = f32::from_bits(self.u32()?);
+        (x.is_finite() && y.is_finite() && x > 0.0 && y > 0.0).then(|| Vector2::new(x, y))
+    }
+}
@@ -0,0 +1,323 @@
+use std::collections::HashMap;
+use std::io;
+use std::net::{SocketAddr, ToSocketAddrs, UdpSocket};
+
+use crate::config;
+use crate::input::Input;
+use crate::net::protocol::{self, MatchInfo, Packet, PROTOCOL_VERSION};
+
+/// The host plays as player one (the left side in versus), the client as player two.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Role {
+    Host,
+    Client,
+}
+
+#[derive(Clone, PartialEq, Eq)]
+pub enum NetStatus {
+    Connecting,
+    Running,
+    Failed(String),
+}
+
+/// One end of a two-peer lockstep match. Each tick runs only once both players' inputs for
+/// it are known; local inputs are scheduled `NET_INPUT_DELAY` ticks ahead to hide latency.
+/// Every packet repeats all inputs the peer has not acknowledged, so a lost packet is
+/// covered by the next one.
+pub struct NetSession {
+    socket: UdpSocket,
+    role: Role,
+    peer: Option<SocketAddr>,
+    info: Option<MatchInfo>,
+    status: NetStatus,
+    tick: u32,
+    accumulator: f32,
+    local_inputs: Vec<i8>,
+    remote_inputs: Vec<Option<i8>>,
+    remote_ack: u32,
+    local_hashes: HashMap<u32, u64>,
+    remote_hashes: HashMap<u32, u64>,
+    latest_hash: Option<(u32, u64)>,
+    last_checked: Option<u32>,
+    silence: f32,
+    stalled: f32,
+    hello_timer: f32,
+    peer_left: bool,
+}
+
+impl NetSession {
+    /// Waits for a player on `NET_PORT`; the match uses `info` once they join.
+    pub fn host(info: MatchInfo) -> io::Result<Self> {
+        let socket = UdpSocket::bind(("0.0.0.0", config::NET_PORT))?;
+        Self::with_socket(socket, Role::Host, None, Some(info))
+    }
+
+    /// `address` is a host name or IP, with `NET_PORT` assumed when no port is given.
+    pub fn join(address: &str) -> io::Result<Self> {
+        let address = address.trim();
+        let with_port = if address.contains(':') {
+            address.to_string()
+        } else {
+            format!("{}:{}", address, config::NET_PORT)
+        };
+        let peer = with_port
+            .to_socket_addrs()?
+            .find(SocketAddr::is_ipv4)
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no IPv4 address"))?;
+        let socket = UdpSocket::bind(("0.0.0.0", 0))?;
+        Self::with_socket(socket, Role::Client, Some(peer), None)
+    }
+
+    fn with_socket(
+        socket: UdpSocket,
+        role: Role,
+        peer: Option<SocketAddr>,
+        info: Option<MatchInfo>,
+    ) -> io::Result<Self> {
+        socket.set_nonblocking(true)?;
+        let delay = config::NET_INPUT_DELAY as usize;
+        Ok(Self {
+            socket,
+            role,
+            peer,
+            info,
+            status: NetStatus::Connecting,
+            tick: 0,
+            accumulator: 0.0,
+            local_inputs: vec![0; delay],
+            remote_inputs: vec![Some(0); delay],
+            remote_ack: 0,
+            local_hashes: HashMap::new(),
+            remote_hashes: HashMap::new(),
+            latest_hash: None,
+            last_checked: None,
+            silence: 0.0,
+            stalled: 0.0,
+            hello_timer: 0.0,
+            peer_left: false,
+        })
+    }
+
+    pub fn info(&self) -> Option<MatchInfo> {
+        self.info
+    }
+
+    pub fn status(&self) -> &NetStatus {
+        &self.status
+    }
+
+    /// True once the simulation has been held up long enough for players to notice.
+    pub fn waiting(&self) -> bool {
+        self.stalled >= config::NET_STALL_NOTICE
+    }
+
+    /// Drains the socket and handles the handshake and timeouts. Call once per frame.
+    pub fn poll(&mut self, dt: f32) {
+        if let NetStatus::Failed(_) = self.status {
+            return;
+        }
+        let mut buf = [0u8; 512];
+        loop {
+            match self.socket.recv_from(&mut buf) {
+                Ok((len, from)) => {
+                    if let Some(packet) = Packet::decode(&buf[..len]) {
+                        self.receive(packet, from);
+                    }
+                }
+                Err(err) if err.kind() == io::ErrorKind::WouldBlock => break,
+                // Windows reports an unreachable peer here; the timeout covers it.
+                Err(err) if err.kind() == io::ErrorKind::ConnectionReset => break,
+                Err(err) => {
+                    self.fail(&format!("Network error: {}", err));
+                    return;
+                }
+            }
+        }
+
+        if self.peer.is_some() {
+            self.silence += dt;
+            if self.silence >= config::NET_TIMEOUT {
+                self.fail("Connection lost");
+                return;
+            }
+        }
+        if self.role == Role::Client && self.status == NetStatus::Connecting {
+            self.hello_timer -= dt;
+            if self.hello_timer <= 0.0 {
+                self.hello_timer = config::NET_HELLO_INTERVAL;
+                self.send(&Packet::Hello {
+                    version: PROTOCOL_VERSION,
+                });
+            }
+        }
+    }
+
+    fn receive(&mut self, packet: Packet, from: SocketAddr) {
+        if self.role == Role::Host && self.peer.is_none() {
+            if let Packet::Hello { .. } = packet {
+                self.peer = Some(from);
+            } else {
+                return;
+            }
+        }
+        if self.peer != Some(from) {
+            return;
+        }
+        self.silence = 0.0;
+
+        match packet {
+            Packet::Hello { version } => {
+                if version != PROTOCOL_VERSION {
+                    self.send(&Packet::Bye);
+                    self.fail("Other player runs a different version");
+                    return;
+                }
+                // Repeated hellos mean our welcome got lost; answer every one.
+                if let Some(info) = self.info {
+                    self.send(&Packet::Welcome { info });
+                    self.status = NetStatus::Running;
+                }
+            }
+            Packet::Welcome { info } => {
+                if self.role == Role::Client && self.status == NetStatus::Connecting {
+                    self.info = Some(info);
+                    self.status = NetStatus::Running;
+                }
+            }
+            Packet::Inputs {
+                ack,
+                first_tick,
+                moves,
+                hash,
+            } => {
+                // The peer can only be a few ticks ahead; anything further is malformed or
+                // spoofed and must not grow the input buffer.
+                let limit = (self.tick + config::NET_INPUT_DELAY) as usize + config::NET_MAX_PACKET_INPUTS;
+                if first_tick as usize + moves.len() > limit {
+                    return;
+                }
+                if let Some((tick, hash)) = hash
+                    && tick as usize <= limit
+                    && self.last_checked.is_none_or(|last| tick > last)
+                {
+                    self.remote_hashes.insert(tick, hash);
+                    self.check_hash(tick);
+                }
+                self.remote_ack = self.remote_ack.max(ack);
+                for (i, value) in moves.into_iter().enumerate() {
+                    let tick = first_tick as usize + i;
+                    if tick >= self.remote_inputs.len() {
+                        self.remote_inputs.resize(tick + 1, None);
+                    }
+                    self.remote_inputs[tick] = Some(value);
+                }
+            }
+            Packet::Bye => {
+                if self.status == NetStatus::Running {
+                    // Inputs already received still get played before giving up.
+                    self.peer_left = true;
+                } else {
+                    self.fail("The other player left");
+                }
+            }
+        }
+    }
+
+    /// Runs as many fixed ticks of `step` as wall time and received inputs allow.
+    /// `step` simulates one tick with both players' inputs and returns the state hash.
+    pub fn advance(&mut self, dt: f32, local: Input, mut step: impl FnMut([Input; 2]) -> u64) {
+        if self.status != NetStatus::Running {
+            return;
+        }
+        let max_backlog = config::NET_TICK * config::NET_MAX_CATCHUP as f32;
+        self.accumulator = (self.accumulator + dt).min(max_backlog);
+        let local_move = protocol::quantize(local);
+        let mut stalled = false;
+        while self.accumulator >= config::NET_TICK {
+            let scheduled = (self.tick + config::NET_INPUT_DELAY) as usize;
+            while self.local_inputs.len() <= scheduled {
+                self.local_inputs.push(local_move);
+            }
+            let tick = self.tick as usize;
+            let Some(Some(remote)) = self.remote_inputs.get(tick).copied() else {
+                stalled = true;
+                break;
+            };
+            let local = protocol::dequantize(self.local_inputs[tick]);
+            let remote = protocol::dequantize(remote);
+            let inputs = match self.role {
+                Role::Host => [local, remote],
+                Role::Client => [remote, local],
+            };
+            let hash = step(inputs);
+            if self.tick.is_multiple_of(config::NET_HASH_INTERVAL) {
+                self.local_hashes.insert(self.tick, hash);
+                self.latest_hash = Some((self.tick, hash));
+                self.check_hash(self.tick);
+            }
+            self.tick += 1;
+            self.accumulator -= config::NET_TICK;
+        }
+
+        if stalled {
+            self.stalled += dt;
+            if self.peer_left {
+                self.fail("The other player left");
+                return;
+            }
+        } else {
+            self.stalled = 0.0;
+        }
+        self.send_inputs();
+    }
+
+    fn send_inputs(&mut self) {
+        let received = self
+            .remote_inputs
+            .iter()
+            .position(Option::is_none)
+            .unwrap_or(self.remote_inputs.len());
+        let first = (self.remote_ack as usize).min(self.local_inputs.len());
+        let last = (first + config::NET_MAX_PACKET_INPUTS).min(self.local_inputs.len());
+        self.send(&Packet::Inputs {
+            ack: received as u32,
+            first_tick: first as u32,
+            moves: self.local_inputs[first..last].to_vec(),
+            hash: self.latest_hash,
+        });
+    }
+
+    /// Compares the two hashes for `tick` once both are in. Anything older than the last
+    /// compared tick can no longer be matched, so it is dropped.
+    fn check_hash(&mut self, tick: u32) {
+        if let (Some(local), Some(remote)) = (self.local_hashes.get(&tick), self.remote_hashes.get(&tick)) {
+            if local != remote {
+                self.fail(&format!("Desync detected at tick {}", tick));
+                return;
+            }
+            self.last_checked = Some(tick);
+            self.local_hashes.retain(|at, _| *at > tick);
+            self.remote_hashes.retain(|at, _| *at > tick);
+        }
+    }
+
+    fn send(&self, packet: &Packet) {
+        if let Some(peer) = self.peer
+            && let Err(err) = self.socket.send_to(&packet.encode(), peer)
+            && err.kind() != io::ErrorKind::WouldBlock
+        {
+            eprintln!("warning: net send failed: {}", err);
+        }
+    }
+
+    fn fail(&mut self, reason: &str) {
+        eprintln!("warning: {}", reason);
+        self.status = NetStatus::Failed(reason.to_string());
+    }
+}
+
+impl Drop for NetSession {
+    fn drop(&mut self) {
+        self.send(&Packet::Bye);
+    }
+}
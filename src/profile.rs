@@ -0,0 +1,77 @@
+use std::collections::BTreeMap;
+use std::fs;
+use crate::config;
+use crate::game::daily::Date;
+
+/// The local player: the name used on leaderboards, which daily challenge was last scored
+/// and the best star rating earned on each campaign level.
+pub struct Profile {
+    pub name: String,
+    last_daily: Option<Date>,
+    stars: BTreeMap<String, u8>,
+}
+
+impl Profile {
+    pub fn load() -> Self {
+        let mut profile = Self {
+            name: String::new(),
+            last_daily: None,
+            stars: BTreeMap::new(),
+        };
+        if let Ok(content) = fs::read_to_string(config::PROFILE_FILE) {
+            for line in content.lines() {
+                match line.split_once('=') {
+                    Some(("name", value)) => profile.name = value.trim().to_string(),
+                    Some(("last_daily", value)) => profile.last_daily = Date::parse(value),
+                    Some((key, value)) if key.starts_with("stars.") => {
+                        if let Ok(stars) = value.trim().parse() {
+                            profile.stars.insert(key["stars.".len()..].to_string(), stars);
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+        profile
+    }
+
+    pub fn save(&self) {
+        let mut content = format!("name={}\n", self.name);
+        if let Some(date) = self.last_daily {
+            content.push_str(&format!("last_daily={}\n", date));
+        }
+        for (level, stars) in &self.stars {
+            content.push_str(&format!("stars.{}={}\n", level, stars));
+        }
+        let _ = fs::write(config::PROFILE_FILE, content);
+    }
+
+    pub fn daily_played(&self, date: Date) -> bool {
+        self.last_daily == Some(date)
+    }
+
+    /// Uses up today's scored attempt as soon as the run starts, so quitting does not reset it.
+    pub fn record_daily(&mut self, date: Date) {
+        self.last_daily = Some(date);
+        self.save();
+    }
+
+    pub fn stars(&self, level_id: &str) -> u8 {
+        self.stars.get(level_id).copied().unwrap_or(0)
+    }
+
+    /// Keeps the best rating so replaying a level never loses stars.
+    pub fn record_stars(&mut self, level_id: &str, stars: u8) {
+        if stars > self.stars(level_id) {
+            self.stars.insert(level_id.to_string(), stars);
+            self.save();
+        }
+    }
+
+    pub fn set_name(&mut self, name: &str) {
+        if self.name != name {
+            self.name = name.to_string();
+            self.save();
+        }
+    }
+}
@@ -0,0 +1,254 @@
+use std::collections::HashMap;
+use raylib::prelude::*;
+use serde::Deserialize;
+use crate::config;
+use crate::game::animator::FrameTimes;
+
+/// Every animated sprite. Each has a PNG and a `.ron` sheet beside it under the assets root.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SpriteId {
+    Bucket,
+    NormalCat,
+    AngelCat,
+    DevilCat,
+    CryingCat,
+}
+
+impl SpriteId {
+    pub const ALL: [SpriteId; 5] = [
+        SpriteId::Bucket,
+        SpriteId::NormalCat,
+        SpriteId::AngelCat,
+        SpriteId::DevilCat,
+        SpriteId::CryingCat,
+    ];
+
+    /// Path of the image under the assets root; the sheet has the same name with `.ron`.
+    pub fn file(self) -> &'static str {
+        match self {
+            SpriteId::Bucket => "cat/bucket.png",
+            SpriteId::NormalCat => "cat/normalneko.png",
+            SpriteId::AngelCat => "cat/angelneko.png",
+            SpriteId::DevilCat => "cat/devilneko.png",
+            SpriteId::CryingCat => "cat/crying_cat.png",
+        }
+    }
+
+    pub fn sheet_file(self) -> String {
+        self.file().replace(".png", ".ron")
+    }
+
+    fn index(self) -> usize {
+        Self::ALL.iter().position(|id| *id == self).unwrap_or(0)
+    }
+}
+
+#[derive(Clone, Copy, Deserialize)]
+struct FrameDef {
+    x: f32,
+    y: f32,
+    w: f32,
+    h: f32,
+}
+
+#[derive(Deserialize)]
+struct AnimationDef {
+    frames: Vec<usize>,
+    /// Seconds each listed frame is shown for. Left out, the game's clip sets the pace.
+    #[serde(default)]
+    durations: Vec<f32>,
+}
+
+/// The `.ron` file describing one sprite image.
+#[derive(Deserialize)]
+pub struct SheetDef {
+    frames: Vec<FrameDef>,
+    #[serde(default)]
+    pivot: (f32, f32),
+    #[serde(default)]
+    animations: HashMap<String, AnimationDef>,
+}
+
+impl SheetDef {
+    /// The whole image as one centred frame, for a sprite whose sheet is missing or broken.
+    pub fn single_frame(width: i32, height: i32) -> Self {
+        Self {
+            frames: vec![FrameDef {
+                x: 0.0,
+                y: 0.0,
+                w: width as f32,
+                h: height as f32,
+            }],
+            pivot: (0.5, 0.5),
+            animations: HashMap::new(),
+        }
+    }
+
+    /// Drops frame times that do not match their frame list or are not positive, and
+    /// names the animations they were dropped from.
+    pub fn drop_bad_durations(&mut self) -> Vec<String> {
+        let mut dropped = Vec::new();
+        for (name, anim) in &mut self.animations {
+            let valid = anim.durations.len() == anim.frames.len()
+                && anim.durations.iter().all(|time| time.is_finite() && *time > 0.0);
+            if !anim.durations.is_empty() && !valid {
+                anim.durations.clear();
+                dropped.push(name.clone());
+            }
+        }
+        dropped
+    }
+}
+
+/// One sprite's frames, already placed in the atlas.
+pub struct SpriteSheet {
+    frames: Vec<Rectangle>,
+    pivot: Vector2,
+    animations: HashMap<String, Vec<usize>>,
+}
+
+impl SpriteSheet {
+    fn new(def: &SheetDef, origin: Vector2) -> Self {
+        Self {
+            frames: def
+                .frames
+                .iter()
+                .map(|f| Rectangle::new(origin.x + f.x, origin.y + f.y, f.w, f.h))
+                .collect(),
+            pivot: Vector2::new(def.pivot.0, def.pivot.1),
+            animations: def
+                .animations
+                .iter()
+                .map(|(name, anim)| (name.clone(), anim.frames.clone()))
+                .collect(),
+        }
+    }
+
+    /// Size of the first frame, which the game uses for hitboxes and layout.
+    pub fn frame_size(&self) -> Vector2 {
+        self.frames
+            .first()
+            .map(|f| Vector2::new(f.width, f.height))
+            .unwrap_or(Vector2::new(1.0, 1.0))
+    }
+
+    /// The atlas rectangle for frame `frame` of `animation`. A short frame list repeats;
+    /// an unknown animation shows the first frame.
+    pub fn frame(&self, animation: &str, frame: usize) -> Rectangle {
+        let index = self
+            .animations
+            .get(animation)
+            .filter(|frames| !frames.is_empty())
+            .map(|frames| frames[frame % frames.len()])
+            .unwrap_or(0);
+        self.frames
+            .get(index)
+            .or(self.frames.first())
+            .copied()
+            .unwrap_or(Rectangle::new(0.0, 0.0, 1.0, 1.0))
+    }
+}
+
+/// All sprite images packed into one texture at startup, with their sheets.
+pub struct Atlas {
+    texture: Texture2D,
+    sheets: Vec<SpriteSheet>,
+    frame_times: FrameTimes,
+}
+
+impl Atlas {
+    /// Packs the images in `SpriteId::ALL` order into rows, tallest first.
+    pub fn build(
+        rl: &mut RaylibHandle,
+        thread: &RaylibThread,
+        sprites: Vec<(Image, SheetDef)>,
+    ) -> Result<Self, String> {
+        let pad = config::ATLAS_PADDING;
+        let mut order: Vec<usize> = (0..sprites.len()).collect();
+        order.sort_by_key(|&i| std::cmp::Reverse(sprites[i].0.height()));
+
+        let mut origins = vec![Vector2::zero(); sprites.len()];
+        let (mut x, mut y, mut row_h, mut width) = (0, 0, 0, 0);
+        for &i in &order {
+            let image = &sprites[i].0;
+            if x > 0 && x + image.width() > config::ATLAS_MAX_WIDTH {
+                x = 0;
+                y += row_h + pad;
+                row_h = 0;
+            }
+            origins[i] = Vector2::new(x as f32, y as f32);
+            x += image.width() + pad;
+            row_h = row_h.max(image.height());
+            width = width.max(x);
+        }
+        let height = y + row_h;
+
+        let mut packed = Image::gen_image_color(width.max(1), height.max(1), Color::BLANK);
+        for ((image, _), origin) in sprites.iter().zip(&origins) {
+            let (w, h) = (image.width() as f32, image.height() as f32);
+            packed.draw(
+                image,
+                Rectangle::new(0.0, 0.0, w, h),
+                Rectangle::new(origin.x, origin.y, w, h),
+                Color::WHITE,
+            );
+        }
+        let texture = rl
+            .load_texture_from_image(thread, &packed)
+            .map_err(|err| err.to_string())?;
+        let sheets = sprites
+            .iter()
+            .zip(&origins)
+            .map(|((_, def), origin)| SpriteSheet::new(def, *origin))
+            .collect();
+        let mut frame_times = FrameTimes::default();
+        for (id, (_, def)) in SpriteId::ALL.into_iter().zip(&sprites) {
+            for (name, anim) in &def.animations {
+                frame_times.insert(id, name, anim.durations.clone());
+            }
+        }
+        Ok(Self { texture, sheets, frame_times })
+    }
+
+    pub fn sheet(&self, id: SpriteId) -> &SpriteSheet {
+        &self.sheets[id.index()]
+    }
+
+    /// The frame times every sheet gives its animations, for the game's animators.
+    pub fn frame_times(&self) -> &FrameTimes {
+        &self.frame_times
+    }
+
+    /// Draws a frame of `animation` with the sheet's pivot at `pos`, scaled and rotated
+    /// around the pivot.
+    pub fn draw(
+        &self,
+        d: &mut RaylibDrawHandle,
+        id: SpriteId,
+        animation: &str,
+        frame: usize,
+        pos: Vector2,
+        scale: f32,
+        rotation: f32,
+        tint: Color,
+    ) {
+        let sheet = self.sheet(id);
+        let source = sheet.frame(animation, frame);
+        let size = Vector2::new(source.width * scale, source.height * scale);
+        let origin = Vector2::new(size.x * sheet.pivot.x, size.y * sheet.pivot.y);
+        d.draw_texture_pro(
+            &self.texture,
+            source,
+            Rectangle::new(pos.x, pos.y, size.x, size.y),
+            origin,
+            rotation,
+            tint,
+        );
+    }
+
+    /// Like `draw`, but stretched to fill `dest` whatever the frame's size.
+    pub fn draw_rect(&self, d: &mut RaylibDrawHandle, id: SpriteId, animation: &str, frame: usize, dest: Rectangle, tint: Color) {
+        let source = self.sheet(id).frame(animation, frame);
+        d.draw_texture_pro(&self.texture, source, dest, Vector2::zero(), 0.0, tint);
+    }
+}
@@ -0,0 +1,86 @@
+use raylib::prelude::*;
+use crate::config;
+use crate::game::events::GameEvent;
+use crate::game::objects::CatKind;
+use crate::game::world::World;
+
+/// The camera one world is drawn through. Big moments add trauma, which shakes the view
+/// by its square and wears off over time; some also punch the zoom in or briefly freeze
+/// the game. Shake and zoom are scaled by the player's motion setting when drawing.
+pub struct CameraRig {
+    trauma: f32,
+    punch: f32,
+    hit_stop: f32,
+    time: f32,
+}
+
+impl CameraRig {
+    pub fn new() -> Self {
+        Self {
+            trauma: 0.0,
+            punch: 0.0,
+            hit_stop: 0.0,
+            time: 0.0,
+        }
+    }
+
+    pub fn react(&mut self, event: GameEvent) {
+        match event {
+            GameEvent::BucketExploded => {
+                self.add_trauma(config::CAMERA_TRAUMA_EXPLOSION);
+                self.punch(config::CAMERA_PUNCH_EXPLOSION);
+            }
+            GameEvent::GiantCatLanded => self.add_trauma(config::CAMERA_TRAUMA_GIANT_CAT),
+            GameEvent::Caught(CatKind::Devil | CatKind::Explode) => {
+                self.add_trauma(config::CAMERA_TRAUMA_DEVIL);
+            }
+            GameEvent::Caught(CatKind::Golden) => {
+                self.hit_stop = self.hit_stop.max(config::HIT_STOP_BIG_CATCH);
+                self.punch(config::CAMERA_PUNCH_BIG_CATCH);
+            }
+            _ => {}
+        }
+    }
+
+    pub fn add_trauma(&mut self, amount: f32) {
+        self.trauma = (self.trauma + amount).min(1.0);
+    }
+
+    pub fn punch(&mut self, zoom: f32) {
+        self.punch = self.punch.max(zoom);
+    }
+
+    /// Real frame time, so shakes wear off even while the game is frozen.
+    pub fn update(&mut self, dt: f32) {
+        self.time += dt;
+        self.trauma = (self.trauma - config::CAMERA_TRAUMA_DECAY * dt).max(0.0);
+        self.punch *= (-config::CAMERA_PUNCH_DECAY * dt).exp();
+        self.hit_stop = (self.hit_stop - dt).max(0.0);
+    }
+
+    /// Whether a hit-stop is holding the game still.
+    pub fn frozen(&self) -> bool {
+        self.hit_stop > 0.0
+    }
+
+    /// `origin_x` is where the world's left edge sits on screen; `motion` scales shake and
+    /// zoom, with 0.0 holding the camera still. The cat's anger adds a steady tremor on top.
+    pub fn camera(&self, world: &World, origin_x: f32, screen_h: f32, motion: f32) -> Camera2D {
+        let anger = ((world.anger_fraction() - config::ANGER_FURIOUS_AT)
+            / (1.0 - config::ANGER_FURIOUS_AT))
+            .clamp(0.0, 1.0);
+        let shake = self.trauma * self.trauma;
+        let amount = (config::CAMERA_SHAKE_MAX_OFFSET * shake + config::ANGER_SHAKE_MAX * anger) * motion;
+        let t = self.time;
+        let center = Vector2::new(world.arena_width() / 2.0, screen_h / 2.0);
+        Camera2D {
+            offset: Vector2::new(
+                origin_x + center.x + (t * 37.0).sin() * amount,
+                center.y + (t * 29.0).cos() * amount,
+            ),
+            target: center,
+            rotation: (t * 23.0).sin() * config::CAMERA_SHAKE_MAX_ANGLE * shake * motion,
+            zoom: 1.0 + self.punch * motion,
+        }
+    }
+}
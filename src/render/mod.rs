@@ -1,86 +1,141 @@
+pub mod atlas;
+pub mod camera;
+pub mod viewport;
+
 use raylib::prelude::*;
+use crate::assets::Assets;
+use crate::render::atlas::{Atlas, SpriteId};
 use crate::config;
+use crate::game::boss::{Boss, BossPhase};
+use crate::game::level::LevelGoal;
+use crate::game::mode::GameMode;
 use crate::game::objects::CatKind;
+use crate::game::particles::Shape;
+use crate::game::rules::RuleSet;
 use crate::game::world::World;
+use crate::settings::Palette;
+
+/// Per-player display preferences for `draw_world`.
+#[derive(Clone, Copy)]
+pub struct DrawOptions {
+    pub landing_markers: bool,
+    pub palette: Palette,
+}
 
 pub fn draw_world(
     d: &mut RaylibDrawHandle,
     world: &World,
-    bucket_texture: &Texture2D,
-    normal_texture: &Texture2D,
-    angel_texture: &Texture2D,
-    devil_texture: &Texture2D,
-    crying_cat_texture: &Texture2D,
+    assets: &Assets,
+    options: DrawOptions,
 ) {
-    let r = world.bucket.rect();
-    let source = Rectangle {
-        x: (world.bucket.frame() as i32 % config::BUCKET_FRAME_COLS) as f32
-            * (bucket_texture.width as f32 / config::BUCKET_FRAME_COLS as f32),
-        y: (world.bucket.frame() as i32 / config::BUCKET_FRAME_COLS) as f32
-            * (bucket_texture.height as f32 / config::BUCKET_FRAME_ROWS as f32),
-        width: bucket_texture.width as f32 / config::BUCKET_FRAME_COLS as f32,
-        height: bucket_texture.height as f32 / config::BUCKET_FRAME_ROWS as f32,
-    };
-    let dest = Rectangle {
-        x: r.x,
-        y: r.y,
-        width: r.width,
-        height: r.height,
-    };
-    d.draw_texture_pro(
-        bucket_texture,
-        source,
-        dest,
-        Vector2::new(0.0, 0.0),
-        0.0,
-        Color::WHITE,
-    );
+    let atlas = assets.atlas();
+    let co_op = world.players.len() > 1;
+    let arena_w = world.arena_width() as i32;
+    for (i, player) in world.players.iter().enumerate() {
+        let bucket = &player.bucket;
+        let r = bucket.rect();
+        let tint = if i == 1 {
+            options.palette.player_two()
+        } else {
+            Color::WHITE
+        };
+        let anim = bucket.animator();
+        let frame = anim.frame(atlas.frame_times());
+        atlas.draw_rect(d, SpriteId::Bucket, anim.clip(), frame, r, tint);
+        if co_op {
+            let tag = format!("P{}", i + 1);
+            let tag_w = d.measure_text(&tag, 16);
+            d.draw_text(
+                &tag,
+                (r.x + r.width / 2.0) as i32 - tag_w / 2,
+                r.y as i32 - 20,
+                16,
+                Color::WHITE,
+            );
+        }
+    }
+    let r = world.bucket().rect();
 
     for obj in &world.objects {
-        let texture = match obj.kind() {
-            CatKind::Normal => normal_texture,
-            CatKind::Angel => angel_texture,
-            CatKind::Devil | CatKind::Explode => devil_texture,
+        if !obj.visible() {
+            continue;
+        }
+        if obj.kind() == CatKind::Heart {
+            draw_heart(d, obj.pos, config::HEART_PICKUP_SIZE, config::COLOR_HEART);
+            continue;
+        }
+        let Some((sprite, tint)) = cat_sprite(obj.kind(), options.palette) else {
+            continue;
         };
-        let frame_w = texture.width as f32 / config::OBJ_FRAME_COLS as f32;
-        let frame_h = texture.height as f32 / config::OBJ_FRAME_ROWS as f32;
-        let source = Rectangle {
-            x: (obj.frame() as i32 % config::OBJ_FRAME_COLS) as f32 * frame_w,
-            y: (obj.frame() as i32 / config::OBJ_FRAME_COLS) as f32 * frame_h,
-            width: frame_w,
-            height: frame_h,
-        };
-        let dest = Rectangle {
-            x: obj.pos.x - (frame_w * config::OBJ_DRAW_SCALE) / 2.0,
-            y: obj.pos.y - (frame_h * config::OBJ_DRAW_SCALE) / 2.0,
-            width: frame_w * config::OBJ_DRAW_SCALE,
-            height: frame_h * config::OBJ_DRAW_SCALE,
+        let draw_scale = config::OBJ_DRAW_SCALE * obj.scale();
+        let anim = obj.animator();
+        let frame = anim.frame(atlas.frame_times());
+        atlas.draw(d, sprite, anim.clip(), frame, obj.pos, draw_scale, 0.0, tint);
+    }
+
+    // Caught cats flatten sideways into the bucket and fade.
+    for obj in world.caught_cats() {
+        let Some((sprite, tint)) = cat_sprite(obj.kind(), options.palette) else {
+            continue;
         };
-        d.draw_texture_pro(
-            texture,
-            source,
-            dest,
-            Vector2::new(0.0, 0.0),
-            0.0,
-            Color::WHITE,
-        );
+        let anim = obj.animator();
+        let t = anim.progress(atlas.frame_times());
+        let size = atlas.sheet(sprite).frame_size() * (config::OBJ_DRAW_SCALE * obj.scale());
+        let w = size.x * (1.0 + t * config::CAT_SQUASH_STRETCH);
+        let h = size.y * (1.0 - t * config::CAT_SQUASH_FLATTEN);
+        let dest = Rectangle::new(obj.pos.x - w / 2.0, obj.pos.y + size.y / 2.0 - h, w, h);
+        let frame = anim.frame(atlas.frame_times());
+        atlas.draw_rect(d, sprite, anim.clip(), frame, dest, tint.alpha(1.0 - t));
+    }
+
+    if let Some(boss) = world.boss() {
+        draw_boss(d, boss, atlas);
+    }
+
+    draw_particles(d, world, atlas);
+
+    if options.landing_markers {
+        for (kind, landing) in world.landing_predictions(world.arena_width()) {
+            let color = match kind {
+                CatKind::Devil | CatKind::Explode | CatKind::Bomb => options.palette.danger(),
+                _ => options.palette.safe(),
+            };
+            d.draw_circle_lines(landing.pos.x as i32, r.y as i32, 6.0, color);
+            d.draw_line(
+                landing.pos.x as i32,
+                r.y as i32 - 10,
+                landing.pos.x as i32,
+                r.y as i32 + 4,
+                color,
+            );
+        }
     }
 
     // Placeholder score text
     let score_text = format!("{}: {}", config::GAME_SCORE_LABEL, world.score());
     d.draw_text(&score_text, 12, 12, 22, Color::WHITE);
 
-    if let Some((pos, t)) = world.explosion_effect() {
-        let radius = 18.0 + t * 90.0;
-        let alpha = ((1.0 - t) * 200.0).clamp(0.0, 200.0) as u8;
-        let color = Color::new(255, 150, 210, alpha);
-        d.draw_circle_lines(pos.x as i32, pos.y as i32, radius, color);
-        d.draw_circle_lines(
-            pos.x as i32,
-            pos.y as i32,
-            radius * 0.6,
-            Color::new(255, 210, 235, alpha),
-        );
+    if co_op {
+        let stats: Vec<String> = world
+            .players
+            .iter()
+            .enumerate()
+            .map(|(i, player)| format!("P{} {}", i + 1, player.stats.caught))
+            .collect();
+        let stats_text = stats.join("  ");
+        let stats_w = d.measure_text(&stats_text, 18);
+        d.draw_text(&stats_text, arena_w - 12 - stats_w, 58, 18, Color::WHITE);
+    }
+
+    if let Some(mode_text) = mode_hud_text(world) {
+        let font_size = 22;
+        let text_w = d.measure_text(&mode_text, font_size);
+        let x = (arena_w - text_w) / 2;
+        d.draw_text(&mode_text, x, 12, font_size, Color::WHITE);
+    }
+
+    if let Some(boss) = world.boss() {
+        draw_boss_bar(d, boss, world.arena_width());
     }
 
     if let Some(message) = world.effect_message() {
@@ -90,7 +145,7 @@ pub fn draw_world(
         let pad_y = 8;
         let box_w = text_w + pad_x * 2;
         let box_h = font_size + pad_y * 2;
-        let box_x = ((config::SCREEN_W - box_w) / 2).max(0);
+        let box_x = ((arena_w - box_w) / 2).max(0);
         let box_y = 50;
         let alpha = (world.effect_message_alpha() * 220.0).clamp(0.0, 220.0) as u8;
         d.draw_rectangle(
@@ -116,51 +171,241 @@ pub fn draw_world(
         );
     }
 
+    match world.rules() {
+        RuleSet::AngryBar => draw_angry_bar(d, world),
+        RuleSet::ClassicLives => draw_hearts(d, world),
+    }
+
+    // Giant Cat
+    if let Some(y) = world.giant_cat_y {
+        atlas.draw(
+            d,
+            SpriteId::CryingCat,
+            world.giant_cat_animator().clip(),
+            world.giant_cat_animator().frame(atlas.frame_times()),
+            Vector2::new(world.arena_width() / 2.0, y),
+            world.giant_cat_scale(),
+            0.0,
+            Color::WHITE,
+        );
+    }
+}
+
+fn draw_particles(d: &mut RaylibDrawHandle, world: &World, atlas: &Atlas) {
+    for p in world.particles().particles() {
+        let (pos, size, color) = (p.pos(), p.size(), p.color());
+        match p.shape() {
+            Shape::Spark => d.draw_rectangle_pro(
+                Rectangle::new(pos.x, pos.y, size, size),
+                Vector2::new(size / 2.0, size / 2.0),
+                p.rotation(),
+                color,
+            ),
+            Shape::Smoke => d.draw_circle_v(pos, size / 2.0, color),
+            Shape::Heart => draw_heart(d, pos, size, color),
+            Shape::Tear => {
+                let r = size / 2.0;
+                d.draw_circle_v(pos, r, color);
+                d.draw_triangle(
+                    Vector2::new(pos.x - r, pos.y),
+                    Vector2::new(pos.x + r, pos.y),
+                    Vector2::new(pos.x, pos.y - size * 1.2),
+                    color,
+                );
+            }
+            Shape::Sprite(sprite) => {
+                let frame_w = atlas.sheet(sprite).frame_size().x;
+                // No animation by that name, so the sheet's first frame.
+                atlas.draw(d, sprite, "", 0, pos, size / frame_w.max(1.0), p.rotation(), color);
+            }
+        }
+    }
+}
+
+/// Which sprite and tint a cat is drawn with; hearts are not sprites.
+fn cat_sprite(kind: CatKind, palette: Palette) -> Option<(SpriteId, Color)> {
+    let tint = match kind {
+        CatKind::Normal | CatKind::Angel | CatKind::Devil | CatKind::Explode => Color::WHITE,
+        CatKind::Golden => config::COLOR_GOLDEN_CAT,
+        CatKind::Ghost => config::COLOR_GHOST_CAT,
+        CatKind::Splitting => config::COLOR_SPLIT_CAT,
+        CatKind::Bomb => palette.bomb_cat(),
+        CatKind::Heart => return None,
+    };
+    Some((kind.sprite(), tint))
+}
+
+/// The Devil Queen is a big devil cat in a crown. She flashes red when hit and spins away
+/// as she falls.
+fn draw_boss(d: &mut RaylibDrawHandle, boss: &Boss, atlas: &Atlas) {
+    let size = config::BOSS_SIZE;
+    let frame_w = atlas.sheet(SpriteId::DevilCat).frame_size().x;
+    let rotation = match boss.phase() {
+        BossPhase::Defeated => (1.0 - boss.fade()) * 270.0,
+        _ => 0.0,
+    };
+    let tint = if boss.flashing() {
+        config::COLOR_BOSS_HIT
+    } else {
+        Color::WHITE
+    };
+    let fade = boss.fade();
+    atlas.draw(
+        d,
+        SpriteId::DevilCat,
+        boss.animator().clip(),
+        boss.animator().frame(atlas.frame_times()),
+        boss.pos,
+        size / frame_w.max(1.0),
+        rotation,
+        tint.alpha(fade),
+    );
+
+    if boss.phase() != BossPhase::Defeated {
+        let crown_y = boss.pos.y - size / 2.0 + 4.0;
+        let crown = config::COLOR_BOSS_CROWN.alpha(fade);
+        for offset in [-14.0, 0.0, 14.0] {
+            let x = boss.pos.x + offset;
+            d.draw_triangle(
+                Vector2::new(x, crown_y - 16.0),
+                Vector2::new(x - 8.0, crown_y),
+                Vector2::new(x + 8.0, crown_y),
+                crown,
+            );
+        }
+        d.draw_rectangle(
+            (boss.pos.x - 22.0) as i32,
+            crown_y as i32,
+            44,
+            6,
+            crown,
+        );
+    }
+}
+
+fn draw_boss_bar(d: &mut RaylibDrawHandle, boss: &Boss, arena_w: f32) {
+    let bar_w = config::BOSS_BAR_WIDTH;
+    let bar_h = config::BOSS_BAR_HEIGHT;
+    let bar_x = (arena_w - bar_w) / 2.0;
+    let bar_y = 38.0;
+    d.draw_rectangle_rec(
+        Rectangle::new(bar_x, bar_y, bar_w, bar_h),
+        config::COLOR_BOSS_BAR_BG,
+    );
+    d.draw_rectangle_rec(
+        Rectangle::new(bar_x, bar_y, bar_w * boss.health_fraction(), bar_h),
+        config::COLOR_BOSS_BAR,
+    );
+    d.draw_rectangle_lines_ex(
+        Rectangle::new(bar_x, bar_y, bar_w, bar_h),
+        1.0,
+        Color::WHITE,
+    );
+    let label = "DEVIL QUEEN";
+    let font_size = 10;
+    let label_w = d.measure_text(label, font_size);
+    d.draw_text(
+        label,
+        (bar_x + (bar_w - label_w as f32) / 2.0) as i32,
+        bar_y as i32,
+        font_size,
+        Color::WHITE,
+    );
+}
+
+fn mode_hud_text(world: &World) -> Option<String> {
+    let clock = |seconds: f32| {
+        let total = seconds.max(0.0).ceil() as i32;
+        format!("{}:{:02}", total / 60, total % 60)
+    };
+    match world.level_goal() {
+        Some(LevelGoal::Survive { seconds }) => {
+            return Some(format!("Survive {}", clock(seconds - world.elapsed_time())));
+        }
+        Some(LevelGoal::Score { points }) => {
+            return Some(format!("Goal {}/{}", world.score().min(points), points));
+        }
+        None => {}
+    }
+    match world.mode() {
+        GameMode::Endless => None,
+        GameMode::TimeAttack { seconds } => {
+            Some(format!("Time {}", clock(seconds as f32 - world.elapsed_time())))
+        }
+        GameMode::Target { points } => Some(format!(
+            "Goal {}/{}  {:.1}s",
+            world.score().min(points),
+            points,
+            world.elapsed_time()
+        )),
+        GameMode::SuddenDeath => Some("Sudden Death".to_string()),
+    }
+}
+
+fn draw_angry_bar(d: &mut RaylibDrawHandle, world: &World) {
     // Angry Bar
     let bar_w = config::ANGRY_BAR_MAX_WIDTH;
     let bar_h = config::ANGRY_BAR_HEIGHT;
     let bar_x = 12.0;
     let bar_y = 60.0;
+    let stage = world.anger_stage();
     
-    let fill_pct = (world.angry_points as f32 / world.max_angry_points as f32).clamp(0.0, 1.0);
-    d.draw_rectangle_lines_ex(Rectangle::new(bar_x, bar_y, bar_w, bar_h), 2.0, config::COLOR_ACCENT_BORDER);
-    d.draw_rectangle(bar_x as i32 + 2, bar_y as i32 + 2, ((bar_w - 4.0) * fill_pct) as i32, (bar_h - 4.0) as i32, Color::RED);
-    d.draw_text("Angry", bar_x as i32, (bar_y - 20.0) as i32, 16, config::COLOR_ACCENT_TEXT);
+    let fill_pct = world.anger_fraction();
+    let bar_rect = Rectangle::new(bar_x, bar_y, bar_w, bar_h);
+    if let Some(pulse) = warning_pulse(d, world) {
+        let glow = Color::new(255, 40, 60, (pulse * 200.0) as u8);
+        d.draw_rectangle_lines_ex(
+            Rectangle::new(bar_x - 3.0, bar_y - 3.0, bar_w + 6.0, bar_h + 6.0),
+            3.0,
+            glow,
+        );
+    }
+    d.draw_rectangle_lines_ex(bar_rect, 2.0, config::COLOR_ACCENT_BORDER);
+    d.draw_rectangle(bar_x as i32 + 2, bar_y as i32 + 2, ((bar_w - 4.0) * fill_pct) as i32, (bar_h - 4.0) as i32, stage.bar_color());
+    d.draw_text(stage.label(), bar_x as i32, (bar_y - 20.0) as i32, 16, config::COLOR_ACCENT_TEXT);
     
     let pct_text = format!("{}%", (fill_pct * 100.0) as i32);
     d.draw_text(&pct_text, (bar_x + bar_w + 8.0) as i32, bar_y as i32, 20, config::COLOR_ACCENT_TEXT);
+}
 
-    // Giant Cat
-    if let Some(y) = world.giant_cat_y {
-         let scale = config::CRYING_CAT_SCALE;
-         let frame_w = config::CRYING_CAT_FRAME_W;
-         let frame_h = config::CRYING_CAT_FRAME_H;
-         
-         let source = Rectangle {
-             x: world.giant_cat_frame as f32 * frame_w,
-             y: 0.0,
-             width: frame_w,
-             height: frame_h,
-         };
-
-         let dest_w = frame_w * scale;
-         let dest_h = frame_h * scale;
-         let dest_x = (d.get_screen_width() as f32 - dest_w) / 2.0;
-
-         let dest = Rectangle {
-             x: dest_x,
-             y: y,
-             width: dest_w,
-             height: dest_h,
-         };
-
-         d.draw_texture_pro(
-            crying_cat_texture,
-            source,
-            dest,
-            Vector2::new(0.0, 0.0),
-            0.0,
-            Color::WHITE
-         );
+/// 0..1 pulse once the run is close to being lost, `None` otherwise.
+fn warning_pulse(d: &RaylibDrawHandle, world: &World) -> Option<f32> {
+    if world.anger_fraction() < config::ANGER_WARNING_AT {
+        return None;
     }
+    let t = d.get_time() as f32 * config::ANGER_PULSE_SPEED;
+    Some(0.5 + 0.5 * t.sin())
+}
+
+fn draw_hearts(d: &mut RaylibDrawHandle, world: &World) {
+    let size = config::HEART_HUD_SIZE;
+    let start_x = 12.0 + size / 2.0;
+    let y = 60.0 + size / 2.0;
+    d.draw_text("Lives", 12, 40, 16, config::COLOR_ACCENT_TEXT);
+    let pulse = warning_pulse(d, world);
+    for i in 0..world.max_lives {
+        let center = Vector2::new(start_x + i as f32 * (size + config::HEART_HUD_GAP), y);
+        let color = if i >= world.lives {
+            config::COLOR_HEART_EMPTY
+        } else if let Some(pulse) = pulse {
+            config::COLOR_HEART.alpha(0.4 + 0.6 * pulse)
+        } else {
+            config::COLOR_HEART
+        };
+        draw_heart(d, center, size, color);
+    }
+}
+
+/// Two circles over a triangle; `size` is the overall width.
+fn draw_heart(d: &mut RaylibDrawHandle, center: Vector2, size: f32, color: Color) {
+    let r = size / 4.0;
+    let top = center.y - size / 4.0;
+    d.draw_circle_v(Vector2::new(center.x - r, top), r, color);
+    d.draw_circle_v(Vector2::new(center.x + r, top), r, color);
+    d.draw_triangle(
+        Vector2::new(center.x - size / 2.0, top + r * 0.3),
+        Vector2::new(center.x, center.y + size / 2.0),
+        Vector2::new(center.x + size / 2.0, top + r * 0.3),
+        color,
+    );
 }
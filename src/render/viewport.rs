@@ -0,0 +1,43 @@
+use raylib::prelude::*;
+use crate::config;
+
+/// Where the fixed virtual screen lands inside the real window: scaled as large as fits,
+/// centred, with black bars on the leftover sides.
+#[derive(Clone, Copy)]
+pub struct Viewport {
+    pub scale: f32,
+    pub offset: Vector2,
+}
+
+impl Viewport {
+    pub fn fit(window_w: f32, window_h: f32) -> Self {
+        let virtual_w = config::SCREEN_W as f32;
+        let virtual_h = config::SCREEN_H as f32;
+        let scale = (window_w / virtual_w).min(window_h / virtual_h).max(0.01);
+        Self {
+            scale,
+            offset: Vector2::new(
+                (window_w - virtual_w * scale) / 2.0,
+                (window_h - virtual_h * scale) / 2.0,
+            ),
+        }
+    }
+
+    /// Window rectangle the virtual screen is drawn into.
+    pub fn dest(&self) -> Rectangle {
+        Rectangle::new(
+            self.offset.x,
+            self.offset.y,
+            config::SCREEN_W as f32 * self.scale,
+            config::SCREEN_H as f32 * self.scale,
+        )
+    }
+
+    /// Maps a window position (e.g. the mouse) into virtual screen coordinates.
+    pub fn to_virtual(self, point: Vector2) -> Vector2 {
+        Vector2::new(
+            (point.x - self.offset.x) / self.scale,
+            (point.y - self.offset.y) / self.scale,
+        )
+    }
+}
@@ -1,19 +1,44 @@
-use raylib::prelude::*;
-
-pub fn get_random_value(min: i32, max: i32) -> i32 {
-    unsafe { raylib::ffi::GetRandomValue(min, max) }
-}
+use std::ops::RangeInclusive;
+use std::time::{SystemTime, UNIX_EPOCH};
 
+/// SplitMix64: tiny, fast and fully determined by its seed, so a run can be replayed.
+#[derive(Clone)]
 pub struct Rng {
-    // placeholder for seeded RNG later
+    state: u64,
 }
 
 impl Rng {
-    pub fn new() -> Self {
-        Self {}
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
     }
 
-    pub fn range_i32(&mut self, rl: &RaylibHandle, range: std::ops::Range<i32>) -> i32 {
-        rl.get_random_value(range)
+    /// Uniform value in `range`, both ends included.
+    pub fn range_i32(&mut self, range: RangeInclusive<i32>) -> i32 {
+        let (min, max) = (*range.start(), *range.end());
+        if max <= min {
+            return min;
+        }
+        let span = (max as i64 - min as i64 + 1) as u64;
+        (min as i64 + (self.next_u64() % span) as i64) as i32
     }
+
+    /// Uniform value in `0.0..1.0`.
+    pub fn unit(&mut self) -> f32 {
+        (self.next_u64() >> 40) as f32 / (1u64 << 24) as f32
+    }
+}
+
+pub fn seed_from_clock() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_nanos() as u64)
+        .unwrap_or(0)
 }
@@ -0,0 +1,350 @@
+use std::fs;
+use std::path::PathBuf;
+use raylib::prelude::*;
+use crate::config;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum FrameLimit {
+    Fps30,
+    Fps60,
+    Fps120,
+    Fps144,
+    Unlimited,
+    VSync,
+}
+
+impl FrameLimit {
+    const ALL: [FrameLimit; 6] = [
+        FrameLimit::Fps30,
+        FrameLimit::Fps60,
+        FrameLimit::Fps120,
+        FrameLimit::Fps144,
+        FrameLimit::Unlimited,
+        FrameLimit::VSync,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            FrameLimit::Fps30 => "30 FPS",
+            FrameLimit::Fps60 => "60 FPS",
+            FrameLimit::Fps120 => "120 FPS",
+            FrameLimit::Fps144 => "144 FPS",
+            FrameLimit::Unlimited => "Unlimited",
+            FrameLimit::VSync => "VSync",
+        }
+    }
+
+    /// `0` leaves the frame rate uncapped.
+    fn target_fps(self) -> u32 {
+        match self {
+            FrameLimit::Fps30 => 30,
+            FrameLimit::Fps60 => 60,
+            FrameLimit::Fps120 => config::TARGET_FPS,
+            FrameLimit::Fps144 => 144,
+            FrameLimit::Unlimited | FrameLimit::VSync => 0,
+        }
+    }
+}
+
+/// Colours that carry meaning (danger markers, the second player) have a colour-blind safe
+/// alternative that does not rely on telling red from green.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Palette {
+    Standard,
+    ColorBlind,
+}
+
+impl Palette {
+    pub fn label(self) -> &'static str {
+        match self {
+            Palette::Standard => "Standard",
+            Palette::ColorBlind => "Colour-blind",
+        }
+    }
+
+    pub fn next(self) -> Self {
+        match self {
+            Palette::Standard => Palette::ColorBlind,
+            Palette::ColorBlind => Palette::Standard,
+        }
+    }
+
+    pub fn safe(self) -> Color {
+        match self {
+            Palette::Standard => config::COLOR_LANDING_MARKER,
+            Palette::ColorBlind => config::COLOR_CB_SAFE,
+        }
+    }
+
+    pub fn danger(self) -> Color {
+        match self {
+            Palette::Standard => config::COLOR_LANDING_MARKER_DANGER,
+            Palette::ColorBlind => config::COLOR_CB_DANGER,
+        }
+    }
+
+    pub fn bomb_cat(self) -> Color {
+        match self {
+            Palette::Standard => config::COLOR_BOMB_CAT,
+            Palette::ColorBlind => config::COLOR_CB_DANGER,
+        }
+    }
+
+    pub fn player_two(self) -> Color {
+        match self {
+            Palette::Standard => config::COLOR_PLAYER_TWO,
+            Palette::ColorBlind => config::COLOR_CB_PLAYER_TWO,
+        }
+    }
+}
+
+/// How much the camera may move, for players who find shake and zoom uncomfortable.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum CameraMotion {
+    Full,
+    Reduced,
+    Off,
+}
+
+impl CameraMotion {
+    pub fn label(self) -> &'static str {
+        match self {
+            CameraMotion::Full => "Full",
+            CameraMotion::Reduced => "Reduced",
+            CameraMotion::Off => "Off",
+        }
+    }
+
+    pub fn next(self) -> Self {
+        match self {
+            CameraMotion::Full => CameraMotion::Reduced,
+            CameraMotion::Reduced => CameraMotion::Off,
+            CameraMotion::Off => CameraMotion::Full,
+        }
+    }
+
+    /// Multiplier for screen shake and zoom punches.
+    pub fn scale(self) -> f32 {
+        match self {
+            CameraMotion::Full => 1.0,
+            CameraMotion::Reduced => config::CAMERA_REDUCED_MOTION,
+            CameraMotion::Off => 0.0,
+        }
+    }
+}
+
+/// Which keys steer the bucket in single-player runs.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum ControlScheme {
+    Both,
+    Wasd,
+    Arrows,
+}
+
+impl ControlScheme {
+    pub fn label(self) -> &'static str {
+        match self {
+            ControlScheme::Both => "A/D + Arrows",
+            ControlScheme::Wasd => "A/D only",
+            ControlScheme::Arrows => "Arrows only",
+        }
+    }
+
+    pub fn next(self) -> Self {
+        match self {
+            ControlScheme::Both => ControlScheme::Wasd,
+            ControlScheme::Wasd => ControlScheme::Arrows,
+            ControlScheme::Arrows => ControlScheme::Both,
+        }
+    }
+}
+
+/// Player options, stored as `key=value` lines in the user's config directory.
+#[derive(Clone, Copy)]
+pub struct Settings {
+    pub music_volume: f32,
+    pub sfx_volume: f32,
+    pub fullscreen: bool,
+    pub window_scale: f32,
+    pub frame_limit: FrameLimit,
+    pub palette: Palette,
+    pub controls: ControlScheme,
+    pub camera_motion: CameraMotion,
+    pub hit_stop: bool,
+}
+
+impl Settings {
+    pub fn load() -> Self {
+        let mut settings = Self {
+            music_volume: 1.0,
+            sfx_volume: 1.0,
+            fullscreen: false,
+            window_scale: 1.0,
+            frame_limit: FrameLimit::Fps120,
+            palette: Palette::Standard,
+            controls: ControlScheme::Both,
+            camera_motion: CameraMotion::Full,
+            hit_stop: true,
+        };
+        let Ok(content) = fs::read_to_string(settings_path()) else {
+            return settings;
+        };
+        for line in content.lines() {
+            let Some((key, value)) = line.split_once('=') else {
+                continue;
+            };
+            let value = value.trim();
+            match key {
+                "music_volume" => {
+                    if let Ok(v) = value.parse::<f32>() {
+                        settings.music_volume = v.clamp(0.0, 1.0);
+                    }
+                }
+                "sfx_volume" => {
+                    if let Ok(v) = value.parse::<f32>() {
+                        settings.sfx_volume = v.clamp(0.0, 1.0);
+                    }
+                }
+                "fullscreen" => settings.fullscreen = value == "true",
+                "window_scale" => {
+                    if let Ok(v) = value.parse::<f32>()
+                        && config::WINDOW_SCALES.contains(&v)
+                    {
+                        settings.window_scale = v;
+                    }
+                }
+                "frame_limit" => {
+                    if let Some(limit) = FrameLimit::ALL.iter().find(|l| l.label() == value) {
+                        settings.frame_limit = *limit;
+                    }
+                }
+                "palette" => {
+                    settings.palette = if value == "colorblind" {
+                        Palette::ColorBlind
+                    } else {
+                        Palette::Standard
+                    };
+                }
+                "controls" => {
+                    settings.controls = match value {
+                        "wasd" => ControlScheme::Wasd,
+                        "arrows" => ControlScheme::Arrows,
+                        _ => ControlScheme::Both,
+                    };
+                }
+                "camera_motion" => {
+                    settings.camera_motion = match value {
+                        "reduced" => CameraMotion::Reduced,
+                        "off" => CameraMotion::Off,
+                        _ => CameraMotion::Full,
+                    };
+                }
+                "hit_stop" => settings.hit_stop = value == "true",
+                _ => {}
+            }
+        }
+        settings
+    }
+
+    pub fn save(&self) {
+        let palette = match self.palette {
+            Palette::Standard => "standard",
+            Palette::ColorBlind => "colorblind",
+        };
+        let controls = match self.controls {
+            ControlScheme::Both => "both",
+            ControlScheme::Wasd => "wasd",
+            ControlScheme::Arrows => "arrows",
+        };
+        let camera_motion = match self.camera_motion {
+            CameraMotion::Full => "full",
+            CameraMotion::Reduced => "reduced",
+            CameraMotion::Off => "off",
+        };
+        let content = format!(
+            "music_volume={:.1}\nsfx_volume={:.1}\nfullscreen={}\nwindow_scale={}\nframe_limit={}\npalette={}\ncontrols={}\ncamera_motion={}\nhit_stop={}\n",
+            self.music_volume,
+            self.sfx_volume,
+            self.fullscreen,
+            self.window_scale,
+            self.frame_limit.label(),
+            palette,
+            controls,
+            camera_motion,
+            self.hit_stop,
+        );
+        let path = settings_path();
+        if let Some(dir) = path.parent()
+            && let Err(err) = fs::create_dir_all(dir)
+        {
+            eprintln!("warning: settings directory {}: {}", dir.display(), err);
+        }
+        if let Err(err) = fs::write(&path, content) {
+            eprintln!("warning: could not save settings to {}: {}", path.display(), err);
+        }
+    }
+
+    pub fn next_volume(volume: f32) -> f32 {
+        let stepped = ((volume + config::VOLUME_STEP) * 10.0).round() / 10.0;
+        if stepped > 1.0 { 0.0 } else { stepped }
+    }
+
+    pub fn next_window_scale(&mut self) {
+        let scales = config::WINDOW_SCALES;
+        let index = scales.iter().position(|s| *s == self.window_scale).unwrap_or(0);
+        self.window_scale = scales[(index + 1) % scales.len()];
+    }
+
+    pub fn next_frame_limit(&mut self) {
+        let index = FrameLimit::ALL
+            .iter()
+            .position(|l| *l == self.frame_limit)
+            .unwrap_or(0);
+        self.frame_limit = FrameLimit::ALL[(index + 1) % FrameLimit::ALL.len()];
+    }
+
+    /// Pushes the window options to raylib; music volume is applied per stream by the app.
+    /// Window size and fullscreen are only touched when they differ from `previous`, so a
+    /// hand-resized window survives unrelated changes.
+    pub fn apply_window(&self, rl: &mut RaylibHandle, previous: Option<&Settings>) {
+        let vsync = WindowState::default().set_vsync_hint(true);
+        if self.frame_limit == FrameLimit::VSync {
+            rl.set_window_state(vsync);
+        } else {
+            rl.clear_window_state(vsync);
+        }
+        rl.set_target_fps(self.frame_limit.target_fps());
+
+        let was_fullscreen = previous.is_some_and(|p| p.fullscreen);
+        if self.fullscreen != was_fullscreen {
+            rl.toggle_borderless_windowed();
+        }
+        let resize = previous.is_none_or(|p| {
+            p.window_scale != self.window_scale || p.fullscreen != self.fullscreen
+        });
+        if resize && !self.fullscreen {
+            rl.set_window_size(
+                (config::SCREEN_W as f32 * self.window_scale) as i32,
+                (config::SCREEN_H as f32 * self.window_scale) as i32,
+            );
+        }
+    }
+}
+
+/// `$XDG_CONFIG_HOME`, `~/.config`, `%APPDATA%` or `~/Library/Application Support`, with the
+/// working directory as a last resort.
+fn settings_path() -> PathBuf {
+    let base = if cfg!(target_os = "windows") {
+        std::env::var_os("APPDATA").map(PathBuf::from)
+    } else if cfg!(target_os = "macos") {
+        std::env::var_os("HOME").map(|home| PathBuf::from(home).join("Library/Application Support"))
+    } else {
+        std::env::var_os("XDG_CONFIG_HOME")
+            .map(PathBuf::from)
+            .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))
+    };
+    match base {
+        Some(dir) => dir.join(config::CONFIG_DIR_NAME).join(config::SETTINGS_FILE),
+        None => PathBuf::from(config::SETTINGS_FILE),
+    }
+}
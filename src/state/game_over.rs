@@ -1,29 +1,80 @@
 use raylib::prelude::*;
 use crate::config;
 use crate::ui;
-use crate::state::leaderboard::LeaderboardState;
+use crate::game::mode::{GameMode, RunEnd, RunResult};
+use crate::state::leaderboard::{Board, LeaderboardState, Unranked};
 
 pub enum GameOverAction {
     None,
     Submit,
+    Continue,
 }
 
 pub struct GameOverState {
     name_buffer: String,
-    score: i32,
+    result: RunResult,
+    board: Result<Board, Unranked>,
 }
 
 impl GameOverState {
     pub fn new() -> Self {
         Self {
             name_buffer: String::new(),
-            score: 0,
+            result: RunResult {
+                mode: GameMode::Endless,
+                score: 0,
+                elapsed: 0.0,
+                end: RunEnd::Quit,
+                stars: None,
+                players: Vec::new(),
+            },
+            board: Err(Unranked::Level),
         }
     }
     
-    pub fn set_score(&mut self, score: i32) {
-        self.score = score;
-        self.name_buffer.clear();
+    /// `board` is where the score goes, or why it goes nowhere.
+    pub fn set_result(&mut self, result: RunResult, board: Result<Board, Unranked>, default_name: &str) {
+        self.result = result;
+        self.board = board;
+        self.name_buffer = default_name.to_string();
+    }
+
+    pub fn board(&self) -> Option<Board> {
+        self.board.ok()
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name_buffer
+    }
+
+    fn headline(&self) -> (&'static str, String) {
+        let result = &self.result;
+        if result.stars.is_some() {
+            let title = match result.end {
+                RunEnd::LevelCleared => "Level Cleared!",
+                RunEnd::OutOfCats => "Out of Cats",
+                _ => "Level Failed",
+            };
+            return (title, format!("Score: {}", result.score));
+        }
+        match (result.mode, result.end) {
+            (GameMode::TimeAttack { .. }, RunEnd::TimeUp) => {
+                ("Time's Up!", format!("Score: {}", result.score))
+            }
+            (GameMode::Target { .. }, RunEnd::TargetReached) => (
+                "Target Reached!",
+                format!("Time: {:.2}s", result.elapsed),
+            ),
+            (GameMode::Target { points }, _) => (
+                "Game Over",
+                format!("Reached {}/{}", result.score.min(points), points),
+            ),
+            (GameMode::SuddenDeath, RunEnd::CatLanded) => (
+                "Sudden Death!",
+                format!("Score: {} in {:.1}s", result.score, result.elapsed),
+            ),
+            _ => ("Game Over", format!("Score: {}", result.score)),
+        }
     }
 
     pub fn update_input(&mut self, rl: &mut RaylibHandle) {
@@ -51,7 +102,7 @@ impl GameOverState {
         // Draw semi-transparent overlay instead so we can see the catastrophe
         // d.draw_rectangle(0, 0, screen_w as i32, screen_h as i32, Color::new(0, 0, 0, 150));
 
-        let title = "Game Over";
+        let (title, score_text) = self.headline();
         let title_size = 40.0;
         let spacing = 1.0;
         let title_w = font.measure_text(title, title_size, spacing).x;
@@ -65,7 +116,6 @@ impl GameOverState {
             config::COLOR_ACCENT_TEXT,
         );
 
-        let score_text = format!("Score: {}", self.score);
         let score_size = 30.0;
         let score_w = font.measure_text(&score_text, score_size, spacing).x;
         d.draw_text_ex(
@@ -77,6 +127,69 @@ impl GameOverState {
             config::COLOR_ACCENT_TEXT,
         );
 
+        if self.result.players.len() > 1 {
+            let stats: Vec<String> = self
+                .result
+                .players
+                .iter()
+                .enumerate()
+                .map(|(i, p)| format!("P{}: {} caught, {} pts", i + 1, p.caught, p.points))
+                .collect();
+            let stats_text = stats.join("   ");
+            let stats_size = 18.0;
+            let stats_w = font.measure_text(&stats_text, stats_size, spacing).x;
+            d.draw_text_ex(
+                font,
+                &stats_text,
+                Vector2::new((screen_w - stats_w) / 2.0, 152.0),
+                stats_size,
+                spacing,
+                config::COLOR_ACCENT_TEXT,
+            );
+        }
+
+        let note = self.board.err().and_then(Unranked::note);
+        if let (Some(stars), None) = (self.result.stars, note) {
+            ui::draw_stars(d, Vector2::new(screen_w / 2.0, 185.0), 20.0, stars);
+        } else if let Some(note) = note {
+            let note_size = 20.0;
+            let note_w = font.measure_text(note, note_size, spacing).x;
+            // Below the per-player stats when there are some.
+            let note_y = if self.result.players.len() > 1 { 180.0 } else { 170.0 };
+            d.draw_text_ex(
+                font,
+                note,
+                Vector2::new((screen_w - note_w) / 2.0, note_y),
+                note_size,
+                spacing,
+                config::COLOR_ACCENT_TEXT,
+            );
+        }
+
+        let (Ok(board), Some(value)) = (self.board, self.result.ranked_value()) else {
+            let continue_rect = Rectangle {
+                x: (screen_w - 180.0) / 2.0,
+                y: 240.0,
+                width: 180.0,
+                height: 48.0,
+            };
+            if ui::draw_button(
+                d,
+                continue_rect,
+                "Continue",
+                mouse,
+                clicked,
+                font,
+                config::COLOR_ACCENT,
+                config::COLOR_ACCENT_HOVER,
+                config::COLOR_ACCENT_TEXT,
+                config::COLOR_ACCENT_BORDER,
+            ) {
+                return GameOverAction::Continue;
+            }
+            return GameOverAction::None;
+        };
+
         // Input Box
         let input_w = 300.0;
         let input_h = 50.0;
@@ -144,7 +257,7 @@ impl GameOverState {
             config::COLOR_ACCENT_BORDER,
         ) {
             if !self.name_buffer.is_empty() {
-                LeaderboardState::add_score(&self.name_buffer, self.score);
+                LeaderboardState::add_score(board, &self.name_buffer, value);
                 return GameOverAction::Submit;
             }
         }
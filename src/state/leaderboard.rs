@@ -1,7 +1,11 @@
 use raylib::prelude::*;
+use std::collections::HashMap;
 use std::fs;
 use std::path::Path;
 use crate::config;
+use crate::game::daily::Date;
+use crate::game::mode::GameMode;
+use crate::game::rules::RuleSet;
 use crate::ui;
 
 #[derive(Clone)]
@@ -10,6 +14,89 @@ pub struct HighScore {
     pub score: i32,
 }
 
+/// Which table a score lives on: one per rule set and game mode, plus one per day for the
+/// daily challenge.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Board {
+    Mode(RuleSet, GameMode),
+    Daily(Date),
+}
+
+/// Why a run's score is not posted to any board.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Unranked {
+    /// A daily challenge played again after the day's first run.
+    DailyReplay,
+    /// The console, the autopilot or a changed timescale touched the run.
+    Debug,
+    CoOp,
+    Lan,
+    /// Campaign levels earn stars instead.
+    Level,
+}
+
+impl Unranked {
+    /// The line shown on the game over screen, if the run needs one.
+    pub fn note(self) -> Option<&'static str> {
+        match self {
+            Unranked::DailyReplay => Some("Practice run - today's daily is already scored"),
+            Unranked::Debug => Some("Debug run - not ranked"),
+            Unranked::CoOp => Some("Co-op run - not ranked"),
+            Unranked::Lan => Some("LAN run - not ranked"),
+            Unranked::Level => None,
+        }
+    }
+}
+
+impl Board {
+    pub fn label(self) -> String {
+        match self {
+            Board::Mode(rules, mode) => format!("{} - {}", rules.label(), mode.label()),
+            Board::Daily(date) => format!("Daily {}", date),
+        }
+    }
+
+    /// Steps through every mode of every rule set, then today's daily board.
+    pub fn next(self) -> Self {
+        self.cycle(1)
+    }
+
+    pub fn prev(self) -> Self {
+        let count = RuleSet::ALL.len() * GameMode::ALL.len() + 1;
+        self.cycle(count - 1)
+    }
+
+    fn cycle(self, step: usize) -> Self {
+        let mut boards: Vec<Board> = RuleSet::ALL
+            .into_iter()
+            .flat_map(|rules| GameMode::ALL.into_iter().map(move |mode| Board::Mode(rules, mode)))
+            .collect();
+        boards.push(Board::Daily(Date::today()));
+        let idx = boards
+            .iter()
+            .position(|board| match (board, self) {
+                (Board::Daily(_), Board::Daily(_)) => true,
+                _ => *board == self,
+            })
+            .unwrap_or(0);
+        boards[(idx + step) % boards.len()]
+    }
+
+    fn lower_is_better(self) -> bool {
+        match self {
+            Board::Mode(_, mode) => mode.lower_is_better(),
+            Board::Daily(_) => false,
+        }
+    }
+
+    fn format_value(self, value: i32) -> String {
+        match self {
+            Board::Mode(_, mode) => mode.format_value(value),
+            Board::Daily(_) => value.to_string(),
+        }
+    }
+}
+
 pub enum LeaderboardAction {
     None,
     Back,
@@ -17,21 +104,36 @@ pub enum LeaderboardAction {
 
 pub struct LeaderboardState {
     high_scores: Vec<HighScore>,
+    board: Board,
 }
 
 impl LeaderboardState {
     pub fn new() -> Self {
-        let mut high_scores = Self::load_scores();
+        let board = Board::Mode(RuleSet::AngryBar, GameMode::Endless);
+        let high_scores = Self::load_scores(board);
         
         Self {
             high_scores,
+            board,
         }
     }
 
-    fn load_scores() -> Vec<HighScore> {
+    fn load_scores(board: Board) -> Vec<HighScore> {
+        let file = match board {
+            Board::Mode(rules, mode) => mode.scores_file(rules),
+            Board::Daily(date) => {
+                let mut scores: Vec<HighScore> = Self::load_daily()
+                    .into_iter()
+                    .filter(|(day, _)| *day == date)
+                    .map(|(_, score)| score)
+                    .collect();
+                Self::sort_scores(board, &mut scores);
+                return scores;
+            }
+        };
         let mut scores = Vec::new();
-        if Path::new(config::SCORES_FILE).exists() {
-            if let Ok(content) = fs::read_to_string(config::SCORES_FILE) {
+        if Path::new(&file).exists() {
+            if let Ok(content) = fs::read_to_string(&file) {
                 for line in content.lines() {
                     // split_once returns (first, rest), so "Name,Score" works
                     if let Some((name, score_str)) = line.rsplit_once(',') {
@@ -45,33 +147,95 @@ impl LeaderboardState {
                 }
             }
         }
-        scores.sort_by(|a, b| b.score.cmp(&a.score));
+        Self::sort_scores(board, &mut scores);
         scores
     }
 
-    pub fn save_scores(scores: &[HighScore]) {
+    /// Daily scores share one file as "date,name,score" lines.
+    fn load_daily() -> Vec<(Date, HighScore)> {
+        let mut scores = Vec::new();
+        if let Ok(content) = fs::read_to_string(config::DAILY_SCORES_FILE) {
+            for line in content.lines() {
+                let Some((date_str, rest)) = line.split_once(',') else {
+                    continue;
+                };
+                if let (Some(date), Some((name, score_str))) =
+                    (Date::parse(date_str), rest.rsplit_once(','))
+                    && let Ok(score) = score_str.trim().parse::<i32>()
+                {
+                    scores.push((
+                        date,
+                        HighScore {
+                            name: name.trim().to_string(),
+                            score,
+                        },
+                    ));
+                }
+            }
+        }
+        scores
+    }
+
+    fn save_daily(scores: &[(Date, HighScore)]) {
+        let mut content = String::new();
+        for (date, score) in scores {
+            content.push_str(&format!("{},{},{}\n", date, score.name, score.score));
+        }
+        let _ = fs::write(config::DAILY_SCORES_FILE, content);
+    }
+
+    fn sort_scores(board: Board, scores: &mut [HighScore]) {
+        if board.lower_is_better() {
+            scores.sort_by(|a, b| a.score.cmp(&b.score));
+        } else {
+            scores.sort_by(|a, b| b.score.cmp(&a.score));
+        }
+    }
+
+    pub fn save_scores(file: &str, scores: &[HighScore]) {
         let mut content = String::new();
         for score in scores {
             content.push_str(&format!("{},{}\n", score.name, score.score));
         }
-        let _ = fs::write(config::SCORES_FILE, content);
+        let _ = fs::write(file, content);
     }
     
     // Add a public method to update scores from outside
-    #[allow(dead_code)]
-    pub fn add_score(name: &str, score: i32) {
-        let mut scores = Self::load_scores();
+    pub fn add_score(board: Board, name: &str, score: i32) {
+        let file = match board {
+            Board::Mode(rules, mode) => mode.scores_file(rules),
+            Board::Daily(date) => {
+                let mut scores = Self::load_daily();
+                scores.push((date, HighScore { name: name.to_string(), score }));
+                scores.sort_by_key(|(_, entry)| std::cmp::Reverse(entry.score));
+                // Keep the top 10 of every day so past dailies stay on record.
+                let mut kept: HashMap<Date, usize> = HashMap::new();
+                scores.retain(|(day, _)| {
+                    let count = kept.entry(*day).or_insert(0);
+                    *count += 1;
+                    *count <= 10
+                });
+                Self::save_daily(&scores);
+                return;
+            }
+        };
+        let mut scores = Self::load_scores(board);
         scores.push(HighScore { name: name.to_string(), score });
-        scores.sort_by(|a, b| b.score.cmp(&a.score));
+        Self::sort_scores(board, &mut scores);
         if scores.len() > 10 {
             scores.truncate(10); // Keep top 10
         }
-        Self::save_scores(&scores);
+        Self::save_scores(&file, &scores);
     }
 
     #[allow(dead_code)]
     pub fn reload(&mut self) {
-        self.high_scores = Self::load_scores();
+        self.high_scores = Self::load_scores(self.board);
+    }
+
+    pub fn show(&mut self, board: Board) {
+        self.board = board;
+        self.reload();
     }
 
     pub fn update_and_draw(
@@ -93,12 +257,57 @@ impl LeaderboardState {
         d.draw_text_ex(
             font,
             title,
-            Vector2::new((screen_w - title_w) / 2.0, 60.0),
+            Vector2::new((screen_w - title_w) / 2.0, 40.0),
             title_size,
             spacing,
             config::COLOR_ACCENT_TEXT,
         );
 
+        let mode_label = self.board.label();
+        let mode_size = 22.0;
+        let mode_w = font.measure_text(&mode_label, mode_size, spacing).x;
+        d.draw_text_ex(
+            font,
+            &mode_label,
+            Vector2::new((screen_w - mode_w) / 2.0, 84.0),
+            mode_size,
+            spacing,
+            config::COLOR_ACCENT_TEXT,
+        );
+
+        let arrow_w = 40.0;
+        // Wide enough for the longest rule set and mode label between the arrows.
+        let prev_rect = Rectangle::new(screen_w / 2.0 - 230.0 - arrow_w, 78.0, arrow_w, 34.0);
+        let next_rect = Rectangle::new(screen_w / 2.0 + 230.0, 78.0, arrow_w, 34.0);
+        if ui::draw_button(
+            d,
+            prev_rect,
+            "<",
+            mouse,
+            clicked,
+            font,
+            config::COLOR_LIGHT_BG,
+            config::COLOR_LIGHT_HOVER,
+            config::COLOR_ACCENT_TEXT,
+            config::COLOR_ACCENT_BORDER,
+        ) {
+            self.show(self.board.prev());
+        }
+        if ui::draw_button(
+            d,
+            next_rect,
+            ">",
+            mouse,
+            clicked,
+            font,
+            config::COLOR_LIGHT_BG,
+            config::COLOR_LIGHT_HOVER,
+            config::COLOR_ACCENT_TEXT,
+            config::COLOR_ACCENT_BORDER,
+        ) {
+            self.show(self.board.next());
+        }
+
         // Draw scores
         if self.high_scores.is_empty() {
             let msg = "Let's start a legendary";
@@ -127,7 +336,7 @@ impl LeaderboardState {
                 // Name
                 d.draw_text_ex(font, &entry.name, Vector2::new(screen_w * 0.35, y), score_font_size, spacing, config::COLOR_ACCENT_TEXT);
                 // Score
-                let score_str = entry.score.to_string();
+                let score_str = self.board.format_value(entry.score);
                 let score_w = font.measure_text(&score_str, score_font_size, spacing).x;
                 d.draw_text_ex(font, &score_str, Vector2::new(screen_w * 0.75 - score_w, y), score_font_size, spacing, config::COLOR_ACCENT_TEXT);
             }
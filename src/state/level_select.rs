@@ -0,0 +1,169 @@
+use raylib::prelude::*;
+use crate::config;
+use crate::game::level::Level;
+use crate::profile::Profile;
+use crate::ui;
+
+pub enum LevelSelectAction {
+    None,
+    Play(usize),
+    Back,
+}
+
+pub struct LevelSelectState {
+    page: usize,
+}
+
+const COLUMNS: usize = 3;
+const ROWS: usize = 3;
+const PER_PAGE: usize = COLUMNS * ROWS;
+
+impl LevelSelectState {
+    pub fn new() -> Self {
+        Self { page: 0 }
+    }
+
+    pub fn update_and_draw(
+        &mut self,
+        d: &mut RaylibDrawHandle,
+        screen_w: f32,
+        screen_h: f32,
+        mouse: Vector2,
+        clicked: bool,
+        font: &WeakFont,
+        levels: &[Level],
+        profile: &Profile,
+    ) -> LevelSelectAction {
+        ui::draw_menu_background(d, screen_w, screen_h);
+
+        let title = "Campaign";
+        let title_size = 32.0;
+        let spacing = 1.0;
+        let title_w = font.measure_text(title, title_size, spacing).x;
+        d.draw_text_ex(
+            font,
+            title,
+            Vector2::new((screen_w - title_w) / 2.0, 40.0),
+            title_size,
+            spacing,
+            config::COLOR_ACCENT_TEXT,
+        );
+
+        if levels.is_empty() {
+            let msg = "No levels found";
+            let msg_size = 24.0;
+            let msg_w = font.measure_text(msg, msg_size, spacing).x;
+            d.draw_text_ex(
+                font,
+                msg,
+                Vector2::new((screen_w - msg_w) / 2.0, screen_h * 0.4),
+                msg_size,
+                spacing,
+                config::COLOR_ACCENT_TEXT,
+            );
+        }
+
+        let pages = levels.len().div_ceil(PER_PAGE).max(1);
+        self.page = self.page.min(pages - 1);
+
+        let card_w = 180.0;
+        let card_h = 52.0;
+        let col_gap = 20.0;
+        let row_h = card_h + 38.0;
+        let grid_w = card_w * COLUMNS as f32 + col_gap * (COLUMNS as f32 - 1.0);
+        let grid_x = (screen_w - grid_w) / 2.0;
+        let grid_y = 96.0;
+
+        let first = self.page * PER_PAGE;
+        for (slot, level) in levels.iter().skip(first).take(PER_PAGE).enumerate() {
+            let index = first + slot;
+            let col = slot % COLUMNS;
+            let row = slot / COLUMNS;
+            let rect = Rectangle::new(
+                grid_x + col as f32 * (card_w + col_gap),
+                grid_y + row as f32 * row_h,
+                card_w,
+                card_h,
+            );
+            let label = format!("{}. {}", index + 1, level.name);
+            if ui::draw_button(
+                d,
+                rect,
+                &label,
+                mouse,
+                clicked,
+                font,
+                config::COLOR_LIGHT_BG,
+                config::COLOR_LIGHT_HOVER,
+                config::COLOR_ACCENT_TEXT,
+                config::COLOR_ACCENT_BORDER,
+            ) {
+                return LevelSelectAction::Play(index);
+            }
+            ui::draw_stars(
+                d,
+                Vector2::new(rect.x + card_w / 2.0, rect.y + card_h + 18.0),
+                9.0,
+                profile.stars(&level.id),
+            );
+        }
+
+        let back_rect = Rectangle {
+            x: (screen_w - 180.0) / 2.0,
+            y: screen_h - 70.0,
+            width: 180.0,
+            height: 48.0,
+        };
+
+        if pages > 1 {
+            let arrow_w = 48.0;
+            let prev_rect = Rectangle::new(back_rect.x - arrow_w - 20.0, back_rect.y, arrow_w, 48.0);
+            let next_rect = Rectangle::new(back_rect.x + back_rect.width + 20.0, back_rect.y, arrow_w, 48.0);
+            if ui::draw_button(
+                d,
+                prev_rect,
+                "<",
+                mouse,
+                clicked,
+                font,
+                config::COLOR_LIGHT_BG,
+                config::COLOR_LIGHT_HOVER,
+                config::COLOR_ACCENT_TEXT,
+                config::COLOR_ACCENT_BORDER,
+            ) {
+                self.page = (self.page + pages - 1) % pages;
+            }
+            if ui::draw_button(
+                d,
+                next_rect,
+                ">",
+                mouse,
+                clicked,
+                font,
+                config::COLOR_LIGHT_BG,
+                config::COLOR_LIGHT_HOVER,
+                config::COLOR_ACCENT_TEXT,
+                config::COLOR_ACCENT_BORDER,
+            ) {
+                self.page = (self.page + 1) % pages;
+            }
+        }
+
+        if ui::draw_button(
+            d,
+            back_rect,
+            "Back",
+            mouse,
+            clicked,
+            font,
+            config::COLOR_LIGHT_BG,
+            config::COLOR_LIGHT_HOVER,
+            config::COLOR_ACCENT_TEXT,
+            config::COLOR_ACCENT_BORDER,
+        ) {
+            return LevelSelectAction::Back;
+        }
+
+        LevelSelectAction::None
+    }
+}
@@ -0,0 +1,196 @@
+use raylib::prelude::*;
+use crate::config;
+use crate::net::protocol::MatchKind;
+use crate::ui;
+
+pub enum LobbyAction {
+    None,
+    Host(MatchKind),
+    Join(String),
+    Cancel,
+    Back,
+}
+
+/// Host/join screen for LAN matches. The host picks versus or co-op; the joining player
+/// types the host's address.
+pub struct LobbyState {
+    address: String,
+    kind: MatchKind,
+    status: String,
+    busy: bool,
+}
+
+impl LobbyState {
+    pub fn new() -> Self {
+        Self {
+            address: config::NET_DEFAULT_ADDRESS.to_string(),
+            kind: MatchKind::Versus,
+            status: String::new(),
+            busy: false,
+        }
+    }
+
+    /// `busy` swaps Host/Join for a Cancel button while a session is being set up.
+    pub fn set_status(&mut self, status: &str, busy: bool) {
+        self.status = status.to_string();
+        self.busy = busy;
+    }
+
+    pub fn update_input(&mut self, rl: &mut RaylibHandle) {
+        while let Some(c) = rl.get_char_pressed() {
+            if self.address.len() < 40 && (c.is_ascii_alphanumeric() || matches!(c, '.' | ':' | '-')) {
+                self.address.push(c);
+            }
+        }
+        if rl.is_key_pressed(KeyboardKey::KEY_BACKSPACE) {
+            self.address.pop();
+        }
+    }
+
+    pub fn update_and_draw(
+        &mut self,
+        d: &mut RaylibDrawHandle,
+        screen_w: f32,
+        screen_h: f32,
+        mouse: Vector2,
+        clicked: bool,
+        font: &WeakFont,
+    ) -> LobbyAction {
+        ui::draw_menu_background(d, screen_w, screen_h);
+
+        let spacing = 1.0;
+        let title = "LAN Play";
+        let title_size = 40.0;
+        let title_w = font.measure_text(title, title_size, spacing).x;
+        d.draw_text_ex(
+            font,
+            title,
+            Vector2::new((screen_w - title_w) / 2.0, 40.0),
+            title_size,
+            spacing,
+            config::COLOR_ACCENT_TEXT,
+        );
+
+        let button_w = 240.0;
+        let button_h = 44.0;
+        let button_x = (screen_w - button_w) / 2.0;
+        let half_w = (button_w - 10.0) / 2.0;
+
+        let kind_rect = Rectangle::new(button_x, 100.0, button_w, button_h);
+        if ui::draw_button(
+            d,
+            kind_rect,
+            self.kind.label(),
+            mouse,
+            clicked && !self.busy,
+            font,
+            config::COLOR_LIGHT_BG,
+            config::COLOR_LIGHT_HOVER,
+            config::COLOR_ACCENT_TEXT,
+            config::COLOR_ACCENT_BORDER,
+        ) {
+            self.kind = self.kind.next();
+        }
+
+        let address_rect = Rectangle::new(button_x, 180.0, button_w, button_h);
+        d.draw_text_ex(
+            font,
+            "Host address:",
+            Vector2::new(button_x, address_rect.y - 24.0),
+            20.0,
+            spacing,
+            config::COLOR_ACCENT_TEXT,
+        );
+        d.draw_rectangle_rec(address_rect, config::COLOR_LIGHT_BG);
+        d.draw_rectangle_lines_ex(address_rect, 2.0, config::COLOR_ACCENT_BORDER);
+        d.draw_text_ex(
+            font,
+            &format!("{}_", self.address),
+            Vector2::new(address_rect.x + 10.0, address_rect.y + 12.0),
+            20.0,
+            spacing,
+            config::COLOR_ACCENT_TEXT,
+        );
+
+        let row_y = address_rect.y + button_h + 16.0;
+        if self.busy {
+            let cancel_rect = Rectangle::new(button_x, row_y, button_w, button_h);
+            if ui::draw_button(
+                d,
+                cancel_rect,
+                "Cancel",
+                mouse,
+                clicked,
+                font,
+                config::COLOR_ACCENT,
+                config::COLOR_ACCENT_HOVER,
+                config::COLOR_ACCENT_TEXT,
+                config::COLOR_ACCENT_BORDER,
+            ) {
+                return LobbyAction::Cancel;
+            }
+        } else {
+            let host_rect = Rectangle::new(button_x, row_y, half_w, button_h);
+            let join_rect = Rectangle::new(button_x + half_w + 10.0, row_y, half_w, button_h);
+            if ui::draw_button(
+                d,
+                host_rect,
+                "Host",
+                mouse,
+                clicked,
+                font,
+                config::COLOR_ACCENT,
+                config::COLOR_ACCENT_HOVER,
+                config::COLOR_ACCENT_TEXT,
+                config::COLOR_ACCENT_BORDER,
+            ) {
+                return LobbyAction::Host(self.kind);
+            }
+            if ui::draw_button(
+                d,
+                join_rect,
+                "Join",
+                mouse,
+                clicked,
+                font,
+                config::COLOR_ACCENT,
+                config::COLOR_ACCENT_HOVER,
+                config::COLOR_ACCENT_TEXT,
+                config::COLOR_ACCENT_BORDER,
+            ) {
+                return LobbyAction::Join(self.address.clone());
+            }
+        }
+
+        if !self.status.is_empty() {
+            let status_size = 18.0;
+            let status_w = font.measure_text(&self.status, status_size, spacing).x;
+            d.draw_text_ex(
+                font,
+                &self.status,
+                Vector2::new((screen_w - status_w) / 2.0, row_y + button_h + 20.0),
+                status_size,
+                spacing,
+                config::COLOR_ACCENT_TEXT,
+            );
+        }
+
+        let back_rect = Rectangle::new(20.0, screen_h - 60.0, 100.0, 40.0);
+        if ui::draw_button(
+            d,
+            back_rect,
+            "Back",
+            mouse,
+            clicked,
+            font,
+            config::COLOR_LIGHT_BG,
+            config::COLOR_LIGHT_HOVER,
+            config::COLOR_ACCENT_TEXT,
+            config::COLOR_ACCENT_BORDER,
+        ) {
+            return LobbyAction::Back;
+        }
+
+        LobbyAction::None
+    }
+}
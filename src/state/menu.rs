@@ -1,18 +1,46 @@
 use raylib::prelude::*;
+use crate::assets::{Assets, TextureId};
 use crate::config;
+use crate::game::mode::GameMode;
+use crate::game::rules::RuleSet;
 use crate::ui;
 
 pub enum MenuAction {
     None,
-    Start,
+    Start { players: usize },
+    Daily,
+    Campaign,
+    Versus,
+    Lan,
     Leaderboard,
+    Settings,
 }
 
-pub struct MenuState;
+pub struct MenuState {
+    rules: RuleSet,
+    mode: GameMode,
+    daily_played: bool,
+}
 
 impl MenuState {
     pub fn new() -> Self {
-        Self
+        Self {
+            rules: RuleSet::AngryBar,
+            mode: GameMode::Endless,
+            daily_played: false,
+        }
+    }
+
+    pub fn set_daily_played(&mut self, played: bool) {
+        self.daily_played = played;
+    }
+
+    pub fn rules(&self) -> RuleSet {
+        self.rules
+    }
+
+    pub fn mode(&self) -> GameMode {
+        self.mode
     }
 
     pub fn update_and_draw(
@@ -23,13 +51,16 @@ impl MenuState {
         mouse: Vector2,
         clicked: bool,
         font: &WeakFont,
-        logo_texture: &Texture2D,
+        assets: &Assets,
     ) -> MenuAction {
+        let logo_texture = assets.texture(TextureId::Logo);
         ui::draw_menu_background(d, screen_w, screen_h);
 
         let button_w = 240.0;
-        let button_h = 56.0;
-        let button_stack_h = button_h * 2.0 + 16.0;
+        let button_h = 40.0;
+        let button_gap = 8.0;
+        let button_count = 7.0;
+        let button_stack_h = button_h * button_count + button_gap * (button_count - 1.0);
         let top_padding = 28.0;
         let logo_gap = 24.0;
         let bottom_padding = 28.0;
@@ -58,16 +89,57 @@ impl MenuState {
             width: button_w,
             height: button_h,
         };
+        let daily_rect = Rectangle {
+            x: button_x,
+            y: start_y + button_h + button_gap,
+            width: button_w,
+            height: button_h,
+        };
+        let campaign_rect = Rectangle {
+            x: button_x,
+            y: start_y + (button_h + button_gap) * 2.0,
+            width: button_w,
+            height: button_h,
+        };
+        let rules_rect = Rectangle {
+            x: button_x,
+            y: start_y + (button_h + button_gap) * 3.0,
+            width: button_w,
+            height: button_h,
+        };
+        let mode_rect = Rectangle {
+            x: button_x,
+            y: start_y + (button_h + button_gap) * 4.0,
+            width: button_w,
+            height: button_h,
+        };
+        let lan_rect = Rectangle {
+            x: button_x,
+            y: start_y + (button_h + button_gap) * 5.0,
+            width: button_w,
+            height: button_h,
+        };
         let leaderboard_rect = Rectangle {
             x: button_x,
-            y: start_y + button_h + 16.0,
+            y: start_y + (button_h + button_gap) * 6.0,
             width: button_w,
             height: button_h,
         };
 
+        // Start shares its row with two-player co-op.
+        let half_w = (button_w - button_gap) / 2.0;
+        let solo_rect = Rectangle {
+            width: half_w,
+            ..start_rect
+        };
+        let co_op_rect = Rectangle {
+            x: start_rect.x + half_w + button_gap,
+            width: half_w,
+            ..start_rect
+        };
         if ui::draw_button(
             d,
-            start_rect,
+            solo_rect,
             "Start",
             mouse,
             clicked,
@@ -77,7 +149,127 @@ impl MenuState {
             config::COLOR_ACCENT_TEXT,
             config::COLOR_ACCENT_BORDER,
         ) {
-            return MenuAction::Start;
+            return MenuAction::Start { players: 1 };
+        }
+
+        if ui::draw_button(
+            d,
+            co_op_rect,
+            "Co-op",
+            mouse,
+            clicked,
+            font,
+            config::COLOR_ACCENT,
+            config::COLOR_ACCENT_HOVER,
+            config::COLOR_ACCENT_TEXT,
+            config::COLOR_ACCENT_BORDER,
+        ) {
+            return MenuAction::Start { players: 2 };
+        }
+
+        let daily_label = if self.daily_played {
+            "Daily (Practice)"
+        } else {
+            "Daily Challenge"
+        };
+        if ui::draw_button(
+            d,
+            daily_rect,
+            daily_label,
+            mouse,
+            clicked,
+            font,
+            config::COLOR_ACCENT,
+            config::COLOR_ACCENT_HOVER,
+            config::COLOR_ACCENT_TEXT,
+            config::COLOR_ACCENT_BORDER,
+        ) {
+            return MenuAction::Daily;
+        }
+
+        // Campaign shares its row with split-screen versus.
+        let versus_rect = Rectangle {
+            x: campaign_rect.x + half_w + button_gap,
+            width: half_w,
+            ..campaign_rect
+        };
+        let campaign_rect = Rectangle {
+            width: half_w,
+            ..campaign_rect
+        };
+        if ui::draw_button(
+            d,
+            campaign_rect,
+            "Campaign",
+            mouse,
+            clicked,
+            font,
+            config::COLOR_ACCENT,
+            config::COLOR_ACCENT_HOVER,
+            config::COLOR_ACCENT_TEXT,
+            config::COLOR_ACCENT_BORDER,
+        ) {
+            return MenuAction::Campaign;
+        }
+
+        if ui::draw_button(
+            d,
+            versus_rect,
+            "Versus",
+            mouse,
+            clicked,
+            font,
+            config::COLOR_ACCENT,
+            config::COLOR_ACCENT_HOVER,
+            config::COLOR_ACCENT_TEXT,
+            config::COLOR_ACCENT_BORDER,
+        ) {
+            return MenuAction::Versus;
+        }
+
+        if ui::draw_button(
+            d,
+            rules_rect,
+            self.rules.label(),
+            mouse,
+            clicked,
+            font,
+            config::COLOR_LIGHT_BG,
+            config::COLOR_LIGHT_HOVER,
+            config::COLOR_ACCENT_TEXT,
+            config::COLOR_ACCENT_BORDER,
+        ) {
+            self.rules = self.rules.next();
+        }
+
+        if ui::draw_button(
+            d,
+            mode_rect,
+            &self.mode.label(),
+            mouse,
+            clicked,
+            font,
+            config::COLOR_LIGHT_BG,
+            config::COLOR_LIGHT_HOVER,
+            config::COLOR_ACCENT_TEXT,
+            config::COLOR_ACCENT_BORDER,
+        ) {
+            self.mode = self.mode.next();
+        }
+
+        if ui::draw_button(
+            d,
+            lan_rect,
+            "LAN Play",
+            mouse,
+            clicked,
+            font,
+            config::COLOR_ACCENT,
+            config::COLOR_ACCENT_HOVER,
+            config::COLOR_ACCENT_TEXT,
+            config::COLOR_ACCENT_BORDER,
+        ) {
+            return MenuAction::Lan;
         }
 
         if ui::draw_button(
@@ -95,6 +287,22 @@ impl MenuState {
             return MenuAction::Leaderboard;
         }
 
+        let settings_rect = Rectangle::new(screen_w - 110.0, 10.0, 100.0, 32.0);
+        if ui::draw_button(
+            d,
+            settings_rect,
+            "Settings",
+            mouse,
+            clicked,
+            font,
+            config::COLOR_LIGHT_BG,
+            config::COLOR_LIGHT_HOVER,
+            config::COLOR_ACCENT_TEXT,
+            config::COLOR_ACCENT_BORDER,
+        ) {
+            return MenuAction::Settings;
+        }
+
         MenuAction::None
     }
 }
@@ -2,3 +2,7 @@ pub mod menu;
 pub mod pause;
 pub mod leaderboard;
 pub mod game_over;
+pub mod level_select;
+pub mod versus_over;
+pub mod lobby;
+pub mod settings;
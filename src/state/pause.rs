@@ -5,6 +5,7 @@ use crate::config;
 pub enum PauseAction {
     None,
     Resume,
+    Settings,
     Exit,
 }
 
@@ -47,8 +48,18 @@ impl PauseState {
             return PauseAction::Resume;
         }
 
+        // Settings Button
+        let settings_rect = Rectangle::new((screen_w - btn_w) / 2.0, 270.0, btn_w, btn_h);
+
+        if ui::draw_button(
+            d, settings_rect, "Settings", mouse, clicked, &font,
+            config::COLOR_LIGHT_BG, config::COLOR_LIGHT_HOVER, config::COLOR_ACCENT_TEXT, config::COLOR_ACCENT_BORDER
+        ) {
+            return PauseAction::Settings;
+        }
+
         // Exit Button
-        let exit_rect = Rectangle::new((screen_w - btn_w) / 2.0, 270.0, btn_w, btn_h);
+        let exit_rect = Rectangle::new((screen_w - btn_w) / 2.0, 340.0, btn_w, btn_h);
 
         if ui::draw_button(
             d, exit_rect, "Exit Game", mouse, clicked, &font,
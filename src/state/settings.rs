@@ -0,0 +1,117 @@
+use raylib::prelude::*;
+use crate::config;
+use crate::settings::Settings;
+use crate::ui;
+
+pub enum SettingsAction {
+    None,
+    Changed,
+    Back,
+}
+
+/// One button per option; clicking a button cycles to the option's next value.
+pub struct SettingsState;
+
+impl SettingsState {
+    pub fn new() -> Self {
+        Self
+    }
+
+    pub fn update_and_draw(
+        &self,
+        d: &mut RaylibDrawHandle,
+        settings: &mut Settings,
+        screen_w: f32,
+        screen_h: f32,
+        mouse: Vector2,
+        clicked: bool,
+        font: &WeakFont,
+    ) -> SettingsAction {
+        ui::draw_menu_background(d, screen_w, screen_h);
+
+        let title = "Settings";
+        let title_size = 36.0;
+        let title_w = font.measure_text(title, title_size, 1.0).x;
+        d.draw_text_ex(
+            font,
+            title,
+            Vector2::new((screen_w - title_w) / 2.0, 20.0),
+            title_size,
+            1.0,
+            config::COLOR_ACCENT_TEXT,
+        );
+
+        let percent = |volume: f32| format!("{}%", (volume * 100.0).round() as i32);
+        let on_off = |on: bool| if on { "On" } else { "Off" };
+        let rows = [
+            format!("Music: {}", percent(settings.music_volume)),
+            format!("Sound FX: {}", percent(settings.sfx_volume)),
+            format!("Fullscreen: {}", on_off(settings.fullscreen)),
+            format!("Window: {}x", settings.window_scale),
+            format!("Frames: {}", settings.frame_limit.label()),
+            format!("Palette: {}", settings.palette.label()),
+            format!("Keys: {}", settings.controls.label()),
+            format!("Screen shake: {}", settings.camera_motion.label()),
+            format!("Hit-stop: {}", on_off(settings.hit_stop)),
+        ];
+
+        let button_w = 280.0;
+        let button_h = 36.0;
+        let button_gap = 6.0;
+        let button_x = (screen_w - button_w) / 2.0;
+        let start_y = 70.0;
+        let mut pressed = None;
+        for (i, label) in rows.iter().enumerate() {
+            let rect = Rectangle::new(
+                button_x,
+                start_y + (button_h + button_gap) * i as f32,
+                button_w,
+                button_h,
+            );
+            if ui::draw_button(
+                d,
+                rect,
+                label,
+                mouse,
+                clicked,
+                font,
+                config::COLOR_LIGHT_BG,
+                config::COLOR_LIGHT_HOVER,
+                config::COLOR_ACCENT_TEXT,
+                config::COLOR_ACCENT_BORDER,
+            ) {
+                pressed = Some(i);
+            }
+        }
+
+        let back_rect = Rectangle::new(20.0, screen_h - 60.0, 100.0, 40.0);
+        if ui::draw_button(
+            d,
+            back_rect,
+            "Back",
+            mouse,
+            clicked,
+            font,
+            config::COLOR_ACCENT,
+            config::COLOR_ACCENT_HOVER,
+            config::COLOR_ACCENT_TEXT,
+            config::COLOR_ACCENT_BORDER,
+        ) {
+            return SettingsAction::Back;
+        }
+
+        match pressed {
+            Some(0) => settings.music_volume = Settings::next_volume(settings.music_volume),
+            Some(1) => settings.sfx_volume = Settings::next_volume(settings.sfx_volume),
+            Some(2) => settings.fullscreen = !settings.fullscreen,
+            Some(3) => settings.next_window_scale(),
+            Some(4) => settings.next_frame_limit(),
+            Some(5) => settings.palette = settings.palette.next(),
+            Some(6) => settings.controls = settings.controls.next(),
+            Some(7) => settings.camera_motion = settings.camera_motion.next(),
+            Some(8) => settings.hit_stop = !settings.hit_stop,
+            _ => return SettingsAction::None,
+        }
+        SettingsAction::Changed
+    }
+}
@@ -0,0 +1,106 @@
+use raylib::prelude::*;
+use crate::config;
+use crate::game::versus::VersusOutcome;
+use crate::ui;
+
+pub enum VersusOverAction {
+    None,
+    Rematch,
+    Menu,
+}
+
+pub struct VersusOverState {
+    outcome: VersusOutcome,
+    scores: [i32; 2],
+}
+
+impl VersusOverState {
+    pub fn new() -> Self {
+        Self {
+            outcome: VersusOutcome::Draw,
+            scores: [0, 0],
+        }
+    }
+
+    pub fn set_result(&mut self, outcome: VersusOutcome, scores: [i32; 2]) {
+        self.outcome = outcome;
+        self.scores = scores;
+    }
+
+    pub fn update_and_draw(
+        &self,
+        d: &mut RaylibDrawHandle,
+        screen_w: f32,
+        screen_h: f32,
+        mouse: Vector2,
+        clicked: bool,
+        font: &WeakFont,
+    ) -> VersusOverAction {
+        ui::draw_menu_background(d, screen_w, screen_h);
+
+        let title = match self.outcome {
+            VersusOutcome::Winner(player) => format!("Player {} wins!", player + 1),
+            VersusOutcome::Draw => "It's a draw!".to_string(),
+        };
+        let spacing = 1.0;
+        let title_size = 40.0;
+        let title_w = font.measure_text(&title, title_size, spacing).x;
+        d.draw_text_ex(
+            font,
+            &title,
+            Vector2::new((screen_w - title_w) / 2.0, 60.0),
+            title_size,
+            spacing,
+            config::COLOR_ACCENT_TEXT,
+        );
+
+        let score_text = format!("P1 {}  -  P2 {}", self.scores[0], self.scores[1]);
+        let score_size = 30.0;
+        let score_w = font.measure_text(&score_text, score_size, spacing).x;
+        d.draw_text_ex(
+            font,
+            &score_text,
+            Vector2::new((screen_w - score_w) / 2.0, 120.0),
+            score_size,
+            spacing,
+            config::COLOR_ACCENT_TEXT,
+        );
+
+        let button_w = 180.0;
+        let button_h = 48.0;
+        let rematch_rect = Rectangle::new((screen_w - button_w) / 2.0, 200.0, button_w, button_h);
+        let menu_rect = Rectangle::new((screen_w - button_w) / 2.0, 260.0, button_w, button_h);
+
+        if ui::draw_button(
+            d,
+            rematch_rect,
+            "Rematch",
+            mouse,
+            clicked,
+            font,
+            config::COLOR_ACCENT,
+            config::COLOR_ACCENT_HOVER,
+            config::COLOR_ACCENT_TEXT,
+            config::COLOR_ACCENT_BORDER,
+        ) {
+            return VersusOverAction::Rematch;
+        }
+
+        if ui::draw_button(
+            d,
+            menu_rect,
+            "Menu",
+            mouse,
+            clicked,
+            font,
+            config::COLOR_LIGHT_BG,
+            config::COLOR_LIGHT_HOVER,
+            config::COLOR_ACCENT_TEXT,
+            config::COLOR_ACCENT_BORDER,
+        ) {
+            return VersusOverAction::Menu;
+        }
+
+        VersusOverAction::None
+    }
+}
@@ -1,7 +1,18 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
 use raylib::prelude::*;
 
 use crate::config;
 
+// Buttons are drawn from many states; rather than thread the sound player through all of
+// them, a press is flagged here and the app turns it into a click sound once per frame.
+static BUTTON_PRESSED: AtomicBool = AtomicBool::new(false);
+
+/// Whether any button was pressed since the last call.
+pub fn take_button_press() -> bool {
+    BUTTON_PRESSED.swap(false, Ordering::Relaxed)
+}
+
 pub fn draw_menu_background(d: &mut RaylibDrawHandle, screen_w: f32, screen_h: f32) {
     d.draw_rectangle_gradient_v(
         0,
@@ -81,5 +92,34 @@ pub fn draw_button(
         text,
     );
 
-    hovered && clicked
+    let pressed = hovered && clicked;
+    if pressed {
+        BUTTON_PRESSED.store(true, Ordering::Relaxed);
+    }
+    pressed
+}
+
+/// A row of three stars centred on `center`, the first `earned` of them filled.
+pub fn draw_stars(d: &mut RaylibDrawHandle, center: Vector2, radius: f32, earned: u8) {
+    let gap = radius * 2.4;
+    for i in 0..3u8 {
+        let x = center.x + (i as f32 - 1.0) * gap;
+        let color = if i < earned {
+            config::COLOR_STAR
+        } else {
+            config::COLOR_STAR_EMPTY
+        };
+        draw_star(d, Vector2::new(x, center.y), radius, color);
+    }
+}
+
+fn draw_star(d: &mut RaylibDrawHandle, center: Vector2, radius: f32, color: Color) {
+    // Triangle fans want counter-clockwise points, which on screen means decreasing angles.
+    let mut points = vec![center];
+    for i in 0..=10 {
+        let angle = -std::f32::consts::FRAC_PI_2 - i as f32 * std::f32::consts::PI / 5.0;
+        let r = if i % 2 == 0 { radius } else { radius * 0.45 };
+        points.push(Vector2::new(center.x + angle.cos() * r, center.y + angle.sin() * r));
+    }
+    d.draw_triangle_fan(&points, color);
 }